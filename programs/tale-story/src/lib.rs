@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 // Program ID from your provided IDL
 declare_id!("8YkcbtdziThWMwYpFnPiW7RHaHHuzr1cTswvm3yT78uS");
@@ -36,13 +38,218 @@ pub enum AppError {
     InvalidScheduleTimes,
     #[msg("Like count overflow.")]
     LikeCountOverflow,
+    #[msg("Like count underflow.")]
+    LikeCountUnderflow,
+    #[msg("Token gate minimum balance must be greater than zero.")]
+    InvalidGateMinBalance,
+    #[msg("Token gate is enabled but has no gate configuration.")]
+    TokenGateNotConfigured,
+    #[msg("Missing token account for an enabled token gate.")]
+    MissingGateTokenAccount,
+    #[msg("Gate token account is not owned by the reader.")]
+    GateTokenOwnerMismatch,
+    #[msg("Gate token account mint does not match the required mint.")]
+    GateTokenMintMismatch,
+    #[msg("Gate token account balance is below the required minimum.")]
+    InsufficientGateBalance,
+    #[msg("Tale is not published.")]
+    TaleNotPublished,
+    #[msg("Collection gates must be a subset of the configured gates.")]
+    InvalidCollectionGates,
+    #[msg("Collection gates require a minimum balance of exactly one token.")]
+    InvalidCollectionGateMinBalance,
+    #[msg("Missing metadata account for a collection token gate.")]
+    MissingGateMetadataAccount,
+    #[msg("Metadata account does not belong to the gate token's mint.")]
+    InvalidGateMetadata,
+    #[msg("Gate token is not a verified member of the required collection.")]
+    GateCollectionMismatch,
+    #[msg("Episode has no schedule transition due yet.")]
+    EpisodeScheduleNotDue,
+    #[msg("Tale status transition is not allowed.")]
+    InvalidTaleStatusTransition,
+    #[msg("Episode status transition is not allowed.")]
+    InvalidEpisodeStatusTransition,
+    #[msg("A scheduled episode requires a publish time.")]
+    MissingPublishTime,
+    #[msg("Scheduled publish time must be in the future.")]
+    PublishTimeInPast,
+    #[msg("Invalid collaborator roles.")]
+    InvalidCollaboratorRoles,
+    #[msg("Only the tale author can grant or revoke the manage collaborators role.")]
+    CannotManageCollaboratorManagers,
+    #[msg("Episode does not belong to the given tale.")]
+    EpisodeTaleMismatch,
+    #[msg("Episode count overflow.")]
+    EpisodeCountOverflow,
+    #[msg("Cannot delete a tale that still has episodes. Close them first.")]
+    TaleHasEpisodes,
+    #[msg("Remaining accounts must be (episode, season or proposal, rent recipient) pairs.")]
+    InvalidCascadeAccounts,
+    #[msg("Account is already at the current layout version.")]
+    AccountAlreadyMigrated,
+    #[msg("Changing episode content requires a revision account.")]
+    RevisionAccountRequired,
+    #[msg("A revision account was passed but the episode content did not change.")]
+    RevisionAccountNotNeeded,
+    #[msg("Revision count overflow.")]
+    RevisionCountOverflow,
+    #[msg("Platform fee exceeds the maximum allowed.")]
+    PlatformFeeTooHigh,
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Too many tip splits.")]
+    TooManyTipSplits,
+    #[msg("Tip splits must have a non-zero share, unique wallets and sum to at most 100%.")]
+    InvalidTipSplit,
+    #[msg("Tip split wallet is not a collaborator on this tale.")]
+    SplitWalletNotCollaborator,
+    #[msg("Mint, payer token account and token program must be passed together for SPL payments.")]
+    IncompleteTokenAccounts,
+    #[msg("Payment recipient does not match the expected wallet or mint.")]
+    InvalidPaymentRecipient,
+    #[msg("Missing payment recipient account.")]
+    MissingPaymentRecipient,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+    #[msg("This episode is not for sale.")]
+    EpisodeNotForSale,
+    #[msg("Episode is not published.")]
+    EpisodeNotPublished,
+    #[msg("Payment mint does not match the episode price.")]
+    PaymentMintMismatch,
+    #[msg("Subscription price and period must be greater than zero.")]
+    InvalidSubscriptionPlan,
+    #[msg("Reader has neither a purchase receipt nor an active subscription for this episode.")]
+    EpisodeAccessDenied,
+    #[msg("Comment body must not be empty.")]
+    EmptyComment,
+    #[msg("Comment body exceeds maximum length.")]
+    CommentTooLong,
+    #[msg("Parent comment does not exist on this episode.")]
+    InvalidParentComment,
+    #[msg("Comment count overflow.")]
+    CommentCountOverflow,
+    #[msg("Comment does not belong to this episode.")]
+    CommentEpisodeMismatch,
+    #[msg("Bookmark limit reached.")]
+    TooManyBookmarks,
+    #[msg("Episode is already bookmarked.")]
+    BookmarkExists,
+    #[msg("Episode is not bookmarked.")]
+    BookmarkNotFound,
+    #[msg("Handle must be 3-32 characters of lowercase letters, digits or underscores.")]
+    InvalidHandle,
+    #[msg("Display name exceeds maximum length.")]
+    DisplayNameTooLong,
+    #[msg("Bio CID exceeds maximum length.")]
+    BioCidTooLong,
+    #[msg("Avatar CID exceeds maximum length.")]
+    AvatarCidTooLong,
+    #[msg("Profile already has a handle.")]
+    ProfileHasHandle,
+    #[msg("Profile has no handle.")]
+    ProfileHasNoHandle,
+    #[msg("Genre slug must be lowercase letters, digits or hyphens.")]
+    InvalidGenreSlug,
+    #[msg("Genre is not active.")]
+    GenreInactive,
+    #[msg("Genre account is required when the genre changes.")]
+    MissingGenreAccount,
+    #[msg("Genre account does not match the genre.")]
+    GenreAccountMismatch,
+    #[msg("Signer is not a platform moderator.")]
+    NotModerator,
+    #[msg("Moderator list is full.")]
+    TooManyModerators,
+    #[msg("Wallet is already a moderator.")]
+    ModeratorExists,
+    #[msg("Wallet is not a moderator.")]
+    ModeratorNotFound,
+    #[msg("Invalid moderation state value.")]
+    InvalidModerationState,
+    #[msg("This content has been removed by a moderator.")]
+    ContentRemoved,
+    #[msg("Invalid report reason value.")]
+    InvalidReportReason,
+    #[msg("Evidence CID exceeds maximum length.")]
+    EvidenceCidTooLong,
+    #[msg("Report has already been resolved or dismissed.")]
+    ReportNotOpen,
+    #[msg("Report does not belong to the passed tale or episode.")]
+    ReportTargetMismatch,
+    #[msg("Signer is not the pending author of this tale.")]
+    NotPendingAuthor,
+    #[msg("No ownership transfer is pending for this tale.")]
+    NoPendingTransfer,
+    #[msg("Tale cannot be transferred to its current author.")]
+    InvalidTransferRecipient,
+    #[msg("Branch label exceeds maximum length.")]
+    BranchLabelTooLong,
+    #[msg("Episode already has the maximum number of branches.")]
+    TooManyBranches,
+    #[msg("Branches must lead to an episode with a higher order.")]
+    InvalidBranchTarget,
+    #[msg("Episode already branches to that episode.")]
+    BranchExists,
+    #[msg("Episode does not branch to that episode.")]
+    BranchNotFound,
+    #[msg("Ending episodes cannot have branches.")]
+    EndingCannotBranch,
+    #[msg("Order cannot change while the episode is linked by branches.")]
+    EpisodeOrderLocked,
+    #[msg("Detach the episode's branches before deleting it.")]
+    EpisodeHasBranches,
+    #[msg("Invalid fork policy value.")]
+    InvalidForkPolicy,
+    #[msg("Revenue share must be between 1 and 10000 basis points.")]
+    InvalidForkShare,
+    #[msg("The upstream tale does not allow forks.")]
+    ForkingDisallowed,
+    #[msg("The upstream author has not approved this fork.")]
+    ForkApprovalRequired,
+    #[msg("Upstream tale account is required to pay the upstream share.")]
+    MissingUpstreamTale,
+    #[msg("Upstream tale account does not match the fork's upstream.")]
+    UpstreamTaleMismatch,
+    #[msg("Proposal count overflow.")]
+    ProposalCountOverflow,
+    #[msg("Cannot delete a tale that still has open proposals. Decide them first.")]
+    TaleHasProposals,
+    #[msg("Open proposal count underflow.")]
+    ProposalCountUnderflow,
+    #[msg("Season still has episodes.")]
+    SeasonNotEmpty,
+    #[msg("Close the tale's seasons before deleting it.")]
+    TaleHasSeasons,
+    #[msg("Remove the episode from its season before deleting it.")]
+    EpisodeInSeason,
+    #[msg("Season account does not match the episode or tale.")]
+    SeasonMismatch,
+    #[msg("This content is hidden by a moderator.")]
+    ContentHidden,
+    #[msg("Treasury balance would drop below the rent-exempt minimum.")]
+    InsufficientTreasuryBalance,
+    #[msg("Episode is already in this season.")]
+    EpisodeAlreadyInSeason,
+    #[msg("Tale predates episode counting, so it cannot be closed without risking orphaned episodes.")]
+    TaleEpisodesUncounted,
+    #[msg("Episode count underflow.")]
+    EpisodeCountUnderflow,
+    #[msg("Season count underflow.")]
+    SeasonCountUnderflow,
+    #[msg("The reported content still exists; resolve or dismiss the report instead.")]
+    ReportTargetExists,
+    #[msg("Inbound branch count underflow.")]
+    BranchCountUnderflow,
 }
 
 // --- Constants for String Lengths ---
 const MAX_TALE_ID_LENGTH: usize = 32;
 const MAX_TITLE_LENGTH: usize = 100;
 const MAX_TALE_CONTENT_CID_LENGTH: usize = 90; // Increased from 64
-const MAX_GENRE_LENGTH: usize = 30;
+const MAX_GENRE_LENGTH: usize = 30; // also the maximum genre slug length
+const MAX_GENRE_DISPLAY_NAME_LENGTH: usize = 50;
 const MAX_COVER_IMAGE_CID_LENGTH: usize = 64;
 const MAX_THUMBNAIL_CID_LENGTH: usize = 64;
 
@@ -53,15 +260,100 @@ const MAX_EPISODE_THUMBNAIL_CID_LENGTH: usize = 64;
 const MAX_IMAGE_SET_ID_LENGTH: usize = 30;
 const MAX_CANDY_MACHINE_ID_LENGTH: usize = 44;
 
+const MAX_COMMENT_BODY_LENGTH: usize = 280; // short text, or an IPFS CID for longer bodies
+
+const MIN_HANDLE_LENGTH: usize = 3;
+const MAX_HANDLE_LENGTH: usize = 32;
+const MAX_DISPLAY_NAME_LENGTH: usize = 50;
+const MAX_BIO_CID_LENGTH: usize = 64;
+const MAX_AVATAR_CID_LENGTH: usize = 64;
+const MAX_EVIDENCE_CID_LENGTH: usize = 64;
+const MAX_BRANCH_LABEL_LENGTH: usize = 50;
+
+// --- Constants for Payments ---
+const BPS_DENOMINATOR: u64 = 10_000;
+const MAX_PLATFORM_FEE_BPS: u16 = 1_000; // 10%
+const MAX_TIP_SPLITS: usize = 4;
+const MAX_MODERATORS: usize = 8;
+
+// --- Constants for Branching ---
+const MAX_EPISODE_BRANCHES: usize = 4;
+// Moderation reason recorded when content is hidden automatically by reader reports.
+pub const AUTO_HIDE_REASON: u16 = u16::MAX;
+
+// --- Constants for Reader Progress ---
+const MAX_BOOKMARKS: usize = 10;
+
+// --- Account Layout Versions ---
+// Bump these whenever a field is appended to the struct. Version 0 means "created before
+// versioning"; `migrate_tale` / `migrate_episode` bring those accounts up to the current layout.
+pub const TALE_ACCOUNT_VERSION: u8 = 1;
+pub const EPISODE_ACCOUNT_VERSION: u8 = 1;
+
+// --- Pre-versioning Layouts ---
+// Tales and episodes created before layout versioning end at `like_count`, and whatever follows
+// it in the account may be stale string bytes. They were allocated at these fixed sizes, and the
+// current layouts are larger, so an account of exactly this size is always a legacy one.
+const LEGACY_TALE_ACCOUNT_SPACE: usize = 536;
+const LEGACY_EPISODE_ACCOUNT_SPACE: usize = 528;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct LegacyTale {
+    author: Pubkey,
+    tale_id: String,
+    title: String,
+    content_cid: String,
+    genre: String,
+    cover_image_cid: String,
+    thumbnail_cid: String,
+    status: u8,
+    timestamp: i64,
+    bump: u8,
+    candy_machine_address: Option<Pubkey>,
+    is_governance_token_gated: bool,
+    is_early_access_token_gated: bool,
+    is_real_world_asset_gated: bool,
+    like_count: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct LegacyEpisode {
+    author: Pubkey,
+    parent_tale: Pubkey,
+    episode_id_seed: String,
+    episode_name: String,
+    content_cid: String,
+    thumbnail_cid: String,
+    image_set_id: String,
+    order: u32,
+    status: u8,
+    is_nft: bool,
+    candy_machine_id: String,
+    timestamp: i64,
+    bump: u8,
+    publish_at_time: Option<i64>,
+    unpublish_at_time: Option<i64>,
+    like_count: u64,
+}
+
 // --- Account Struct Definitions ---
+// New fields must only ever be appended, so an old account reallocated by
+// `migrate_tale` / `migrate_episode` still deserializes.
 #[account]
+#[derive(InitSpace)]
 pub struct Tale {
     pub author: Pubkey,
+    #[max_len(MAX_TALE_ID_LENGTH)]
     pub tale_id: String,
+    #[max_len(MAX_TITLE_LENGTH)]
     pub title: String,
-    pub content_cid: String, // Max length defined by MAX_TALE_CONTENT_CID_LENGTH
+    #[max_len(MAX_TALE_CONTENT_CID_LENGTH)]
+    pub content_cid: String,
+    #[max_len(MAX_GENRE_LENGTH)]
     pub genre: String,
+    #[max_len(MAX_COVER_IMAGE_CID_LENGTH)]
     pub cover_image_cid: String,
+    #[max_len(MAX_THUMBNAIL_CID_LENGTH)]
     pub thumbnail_cid: String,
     pub status: u8,
     pub timestamp: i64,
@@ -71,338 +363,5044 @@ pub struct Tale {
     pub is_early_access_token_gated: bool,
     pub is_real_world_asset_gated: bool,
     pub like_count: u64,
+    pub governance_gate: Option<TokenGate>,
+    pub early_access_gate: Option<TokenGate>,
+    pub real_world_asset_gate: Option<TokenGate>,
+    pub collection_gates: u8, // `GATE_*` bits whose gate `mint` is a collection
+    pub episode_count: u32,
+    pub version: u8,
+    pub total_tips: u64,
+    pub total_tip_lamports: u64,
+    #[max_len(MAX_TIP_SPLITS)]
+    pub tip_splits: Vec<TipSplit>,
+    pub author_profile: Option<Pubkey>,
+    pub moderation_state: u8,
+    pub moderation_reason: u16,
+    pub report_count: u32, // open reports only
+    pub pending_author: Option<Pubkey>,
+    // Bumped by every ownership transfer; `TaleCollaborator` records stamped with an earlier
+    // generation grant nothing until a manager updates them.
+    pub collaborator_generation: u32,
+    // `fork_share_bps` is what forks created under `RevenueShare` will owe;
+    // `upstream_share_bps` is what this tale, if it is a fork, owes `upstream_tale`.
+    pub fork_policy: u8,
+    pub fork_share_bps: u16,
+    pub upstream_tale: Option<Pubkey>,
+    pub upstream_share_bps: u16,
+    pub proposal_count: u32,
+    pub open_proposal_count: u32, // proposals not yet accepted or rejected
+    // Seasons are numbered from `next_season_index`; `season_count` is how many are open.
+    pub next_season_index: u32,
+    pub season_count: u32,
+    // The wallet whose namespace the PDA is derived in, `[b"tale", creator, tale_id]`.
+    // `None` for tales created under the legacy global `[b"tale", tale_id]` derivation. Those are
+    // migrated in place rather than moved: episodes, collaborators, passes, subscriptions and every
+    // other tale record are seeded by the tale's address, so moving it would orphan all of them.
+    // New tales never use the global derivation, so legacy IDs no longer block other authors.
+    pub creator: Option<Pubkey>,
+}
+
+const TALE_ACCOUNT_SPACE: usize = 8 + Tale::INIT_SPACE;
+
+impl Tale {
+    // The middle PDA seed. Legacy tales use an empty seed, which derives the same address as
+    // leaving it out, so one `seeds` constraint covers both derivations.
+    pub fn seed_namespace(&self) -> &[u8] {
+        self.creator.as_ref().map_or(&[], |creator| creator.as_ref())
+    }
+
+    // Legacy tales may still have episodes that were never migrated, and so never counted, and
+    // there is no way to enumerate them on-chain. Their `episode_count` is only a lower bound, so
+    // `delete_tale` and `close_tale_cascade` never close them.
+    pub fn has_uncounted_episodes(&self) -> bool {
+        self.creator.is_none()
+    }
+}
+
+// Share of every tip (after the platform fee) routed to a collaborator instead of the author.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TipSplit {
+    pub wallet: Pubkey,
+    pub share_bps: u16,
+}
+
+// Requirement for one of the tale's token gates: the reader must hold at least
+// `min_balance` of `mint` in a token account they own. When the gate's bit is set in
+// `Tale.collection_gates`, `mint` is instead a collection: the reader must hold one token
+// whose metadata names it as a verified collection, and `min_balance` must be 1.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub min_balance: u64,
+}
+
+// Owner of the metadata accounts that record an NFT's verified collection.
+const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Leading fields of a Token Metadata account, up to and including the collection.
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+struct MetadataPrefix {
+    key: u8,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<MetadataCreator>>,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    token_standard: Option<u8>,
+    collection: Option<MetadataCollection>,
+}
+
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+struct MetadataCreator {
+    address: Pubkey,
+    verified: bool,
+    share: u8,
 }
 
-// Recalculated Space for Tale:
-// Discriminator: 8
-// author: 32
-// tale_id: 4 + MAX_TALE_ID_LENGTH (32) = 36
-// title: 4 + MAX_TITLE_LENGTH (100) = 104
-// content_cid: 4 + MAX_TALE_CONTENT_CID_LENGTH (90) = 94  <-- Changed
-// genre: 4 + MAX_GENRE_LENGTH (30) = 34
-// cover_image_cid: 4 + MAX_COVER_IMAGE_CID_LENGTH (64) = 68
-// thumbnail_cid: 4 + MAX_THUMBNAIL_CID_LENGTH (64) = 68
-// status: 1
-// timestamp: 8
-// bump: 1
-// candy_machine_address: 1 (Option) + 32 (Pubkey) = 33
-// is_governance_token_gated: 1
-// is_early_access_token_gated: 1
-// is_real_world_asset_gated: 1
-// like_count: 8
-// Sum of fields = 8+32+36+104+94+34+68+68+1+8+1+33+1+1+1+8 = 490 bytes
-// Buffer: Let's use 38 to make it 528 (divisible by 8)
-const TALE_ACCOUNT_SPACE: usize = 490 + 38 + 8; // Total 536 bytes (add 8 for u64 like_count)
+#[derive(AnchorDeserialize)]
+struct MetadataCollection {
+    verified: bool,
+    key: Pubkey,
+}
 
 #[account]
+#[derive(InitSpace)]
 pub struct Episode {
+    // Creator and rent payer only; permissions always come from the parent tale's current author.
     pub author: Pubkey,
     pub parent_tale: Pubkey,
+    #[max_len(MAX_EPISODE_ID_SEED_LENGTH)]
     pub episode_id_seed: String,
+    #[max_len(MAX_EPISODE_NAME_LENGTH)]
     pub episode_name: String,
+    #[max_len(MAX_EPISODE_CONTENT_CID_LENGTH)]
     pub content_cid: String,
+    #[max_len(MAX_EPISODE_THUMBNAIL_CID_LENGTH)]
     pub thumbnail_cid: String,
+    #[max_len(MAX_IMAGE_SET_ID_LENGTH)]
     pub image_set_id: String,
     pub order: u32,
     pub status: u8,
     pub is_nft: bool,
+    #[max_len(MAX_CANDY_MACHINE_ID_LENGTH)]
     pub candy_machine_id: String,
     pub timestamp: i64,
     pub bump: u8,
     pub publish_at_time: Option<i64>,
     pub unpublish_at_time: Option<i64>,
     pub like_count: u64,
+    pub version: u8,
+    pub revision_count: u32,
+    // A zero price means the episode is free; `price_mint` is `None` for lamports.
+    pub price: u64,
+    pub price_mint: Option<Pubkey>,
+    pub comment_count: u32,
+    pub moderation_state: u8,
+    pub moderation_reason: u16,
+    pub report_count: u32, // open reports only
+    #[max_len(MAX_EPISODE_BRANCHES)]
+    pub branches: Vec<EpisodeBranch>,
+    pub parent_episode: Option<Pubkey>,
+    pub inbound_branch_count: u16,
+    pub is_ending: bool,
+    pub co_author: Option<Pubkey>, // the contributor of an accepted proposal
+    pub season: Option<Pubkey>,
 }
 
-const EPISODE_ACCOUNT_SPACE: usize = 503 + 25; // Total 528 bytes
+const EPISODE_ACCOUNT_SPACE: usize = 8 + Episode::INIT_SPACE;
 
-// --- Enums for Status ---
-#[repr(u8)]
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum TaleStatus { Draft = 0, Published = 1, Archived = 2 }
+impl Episode {
+    // Branches always lead to a higher `order`, which keeps the graph acyclic; so the order of any
+    // episode at either end of a branch is frozen until the branch is detached.
+    fn set_order(&mut self, order: u32) -> Result<()> {
+        if order != self.order && (!self.branches.is_empty() || self.inbound_branch_count > 0) {
+            return err!(AppError::EpisodeOrderLocked);
+        }
+        self.order = order;
+        Ok(())
+    }
+}
 
-#[repr(u8)]
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum EpisodeStatus {
-    Draft = 0,
-    Published = 1,
-    Scheduled = 2,
-    Archived = 3,
+// Groups episodes of a tale. `status` uses the `TaleStatus` values and transitions.
+// `created_by` paid the rent and gets it back when the season is closed.
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub tale: Pubkey,
+    pub index: u32,
+    #[max_len(MAX_TITLE_LENGTH)]
+    pub title: String,
+    #[max_len(MAX_COVER_IMAGE_CID_LENGTH)]
+    pub cover_image_cid: String,
+    pub status: u8,
+    pub episode_count: u32,
+    pub created_by: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
 }
 
-#[program]
-pub mod tale_story {
-    use super::*;
+const SEASON_ACCOUNT_SPACE: usize = 8 + Season::INIT_SPACE;
 
-    pub fn create_tale(
-        ctx: Context<CreateTale>,
-        tale_id: String,
-        title: String,
-        content_cid: String, // This argument will be checked against MAX_TALE_CONTENT_CID_LENGTH
-        genre: String,
-        cover_image_cid: String,
-        thumbnail_cid: String,
-        initial_status: u8,
-        candy_machine_address_option: Option<Pubkey>,
-        is_governance: bool,
-        is_early_access: bool,
-        is_real_world: bool,
-    ) -> Result<()> {
-        if tale_id.len() > MAX_TALE_ID_LENGTH { return err!(AppError::EpisodeIdSeedTooLong); }
-        if title.len() > MAX_TITLE_LENGTH { return err!(AppError::TitleTooLong); }
-        if content_cid.len() > MAX_TALE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Error originates here
-        if genre.len() > MAX_GENRE_LENGTH { return err!(AppError::GenreTooLong); }
-        if cover_image_cid.len() > MAX_COVER_IMAGE_CID_LENGTH { return err!(AppError::CoverImageCidTooLong); }
-        if thumbnail_cid.len() > MAX_THUMBNAIL_CID_LENGTH { return err!(AppError::ThumbnailCidTooLong); }
-        if initial_status > TaleStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
+// A community-written episode waiting for the tale's author. Proposals are numbered per tale
+// from 0 up to `Tale.proposal_count - 1` and closed, refunding the proposer, once decided.
+#[account]
+#[derive(InitSpace)]
+pub struct EpisodeProposal {
+    pub tale: Pubkey,
+    pub index: u32,
+    pub proposer: Pubkey,
+    #[max_len(MAX_EPISODE_NAME_LENGTH)]
+    pub episode_name: String,
+    #[max_len(MAX_EPISODE_CONTENT_CID_LENGTH)]
+    pub content_cid: String,
+    #[max_len(MAX_EPISODE_THUMBNAIL_CID_LENGTH)]
+    pub thumbnail_cid: String,
+    pub submitted_at: i64,
+    pub bump: u8,
+}
 
-        let tale = &mut ctx.accounts.tale_account;
-        tale.author = *ctx.accounts.author.key;
-        tale.tale_id = tale_id;
-        tale.title = title;
-        tale.content_cid = content_cid;
-        tale.genre = genre;
-        tale.cover_image_cid = cover_image_cid;
-        tale.thumbnail_cid = thumbnail_cid;
-        tale.status = initial_status;
-        tale.timestamp = Clock::get()?.unix_timestamp;
-        tale.bump = ctx.bumps.tale_account;
-        tale.candy_machine_address = candy_machine_address_option;
-        tale.is_governance_token_gated = is_governance;
-        tale.is_early_access_token_gated = is_early_access;
-        tale.is_real_world_asset_gated = is_real_world;
-        tale.like_count = 0;
-        msg!("Tale created: {}, Content CID: {}, Thumbnail: {}", tale.title, tale.content_cid, tale.thumbnail_cid);
-        Ok(())
+const EPISODE_PROPOSAL_ACCOUNT_SPACE: usize = 8 + EpisodeProposal::INIT_SPACE;
+
+// A reader choice leading from one episode to `next_episode` in the same tale.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct EpisodeBranch {
+    #[max_len(MAX_BRANCH_LABEL_LENGTH)]
+    pub label: String,
+    pub next_episode: Pubkey,
+}
+
+// Snapshot of an episode's content as it was before revision `revision` replaced it.
+// Revisions are numbered from 0 up to `Episode.revision_count - 1`.
+#[account]
+#[derive(InitSpace)]
+pub struct EpisodeRevision {
+    pub episode: Pubkey,
+    pub revision: u32,
+    #[max_len(MAX_EPISODE_CONTENT_CID_LENGTH)]
+    pub content_cid: String,
+    #[max_len(MAX_EPISODE_THUMBNAIL_CID_LENGTH)]
+    pub thumbnail_cid: String,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+const EPISODE_REVISION_ACCOUNT_SPACE: usize = 8 + EpisodeRevision::INIT_SPACE;
+
+// Comments are numbered per episode from 0 up to `Episode.comment_count - 1`. Indices are never
+// reused, so a reply whose `parent` was deleted still points at a stable (now empty) slot.
+#[account]
+#[derive(InitSpace)]
+pub struct Comment {
+    pub episode: Pubkey,
+    pub index: u32,
+    pub author: Pubkey,
+    pub parent: Option<u32>,
+    #[max_len(MAX_COMMENT_BODY_LENGTH)]
+    pub body: String,
+    pub is_hidden: bool,
+    pub created_at: i64,
+    pub edited_at: Option<i64>,
+    pub bump: u8,
+}
+
+const COMMENT_ACCOUNT_SPACE: usize = 8 + Comment::INIT_SPACE;
+
+// A reader's position in a tale. `episodes_read` only counts episodes that moved `highest_order`
+// forward, so it can never exceed the number of distinct episodes read; once it reaches the tale's
+// `episode_count`, `completed_at` is stamped and never cleared.
+#[account]
+#[derive(InitSpace)]
+pub struct ReaderProgress {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub last_episode: Pubkey,
+    pub last_order: u32,
+    pub highest_order: Option<u32>,
+    pub episodes_read: u32,
+    #[max_len(MAX_BOOKMARKS)]
+    pub bookmarks: Vec<Pubkey>,
+    pub completed_at: Option<i64>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+const READER_PROGRESS_ACCOUNT_SPACE: usize = 8 + ReaderProgress::INIT_SPACE;
+
+// One report per (target, reporter), so a single wallet cannot flood the queue. The target is a
+// Tale or an Episode; `tale` is always the tale involved so moderators can filter by it.
+// Resolving or dismissing closes it and refunds the reporter, who may then report again.
+#[account]
+#[derive(InitSpace)]
+pub struct ContentReport {
+    pub target: Pubkey,
+    pub tale: Pubkey,
+    pub reporter: Pubkey,
+    pub reason: u8,
+    #[max_len(MAX_EVIDENCE_CID_LENGTH)]
+    pub evidence_cid: String,
+    pub status: u8,
+    pub created_at: i64,
+    pub resolved_by: Option<Pubkey>,
+    pub resolved_at: Option<i64>,
+    pub bump: u8,
+}
+
+const CONTENT_REPORT_ACCOUNT_SPACE: usize = 8 + ContentReport::INIT_SPACE;
+
+// Lets `forker` fork `upstream` when its policy is `RequiresApproval`. Revoking closes it.
+#[account]
+#[derive(InitSpace)]
+pub struct ForkApproval {
+    pub upstream: Pubkey,
+    pub forker: Pubkey,
+    pub approved_at: i64,
+    pub bump: u8,
+}
+
+const FORK_APPROVAL_ACCOUNT_SPACE: usize = 8 + ForkApproval::INIT_SPACE;
+
+// Public identity of a wallet. One per wallet; the handle is optional and owned through `Handle`.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorProfile {
+    pub wallet: Pubkey,
+    #[max_len(MAX_HANDLE_LENGTH)]
+    pub handle: Option<String>,
+    #[max_len(MAX_DISPLAY_NAME_LENGTH)]
+    pub display_name: String,
+    #[max_len(MAX_BIO_CID_LENGTH)]
+    pub bio_cid: String,
+    #[max_len(MAX_AVATAR_CID_LENGTH)]
+    pub avatar_cid: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+const AUTHOR_PROFILE_ACCOUNT_SPACE: usize = 8 + AuthorProfile::INIT_SPACE;
+
+// Seeded by the lowercase handle, so each handle can be held by at most one wallet.
+#[account]
+#[derive(InitSpace)]
+pub struct Handle {
+    #[max_len(MAX_HANDLE_LENGTH)]
+    pub handle: String,
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+const HANDLE_ACCOUNT_SPACE: usize = 8 + Handle::INIT_SPACE;
+
+// One record per (target, user). The target is either a Tale or an Episode account.
+#[account]
+#[derive(InitSpace)]
+pub struct LikeRecord {
+    pub target: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+const LIKE_RECORD_ACCOUNT_SPACE: usize = 8 + LikeRecord::INIT_SPACE;
+
+// Issued by `grant_read_access` once the reader has passed every enabled gate on the tale.
+// The backend verifies this PDA instead of trusting the frontend. `granted_at` is refreshed
+// on every successful re-check so stale passes can be rejected off-chain.
+#[account]
+#[derive(InitSpace)]
+pub struct AccessPass {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+const ACCESS_PASS_ACCOUNT_SPACE: usize = 8 + AccessPass::INIT_SPACE;
+
+// Receipt issued by `purchase_episode`. The backend serves a paid episode's `content_cid`
+// only to readers whose receipt PDA exists. `amount` and `mint` record what was paid.
+#[account]
+#[derive(InitSpace)]
+pub struct EpisodePurchase {
+    pub episode: Pubkey,
+    pub reader: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub purchased_at: i64,
+    pub bump: u8,
+}
+
+const EPISODE_PURCHASE_ACCOUNT_SPACE: usize = 8 + EpisodePurchase::INIT_SPACE;
+
+// One plan per tale, set by its author. Changes apply to later subscriptions and renewals only.
+#[account]
+#[derive(InitSpace)]
+pub struct SubscriptionPlan {
+    pub tale: Pubkey,
+    pub price: u64,
+    pub period_seconds: i64,
+    pub mint: Option<Pubkey>,
+    pub bump: u8,
+}
+
+const SUBSCRIPTION_PLAN_ACCOUNT_SPACE: usize = 8 + SubscriptionPlan::INIT_SPACE;
+
+// A reader's subscription to a tale. It grants access to every episode while `expires_at` is in the future.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub started_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+const SUBSCRIPTION_ACCOUNT_SPACE: usize = 8 + Subscription::INIT_SPACE;
+
+// Grants a wallet a subset of the author's powers on a single tale.
+#[account]
+#[derive(InitSpace)]
+pub struct TaleCollaborator {
+    pub tale: Pubkey,
+    pub wallet: Pubkey,
+    pub roles: u8,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+    pub generation: u32, // the tale's `collaborator_generation` when the roles were last granted
+}
+
+const TALE_COLLABORATOR_ACCOUNT_SPACE: usize = 8 + TaleCollaborator::INIT_SPACE;
+
+// Registry entry for a genre. `Tale.genre` holds the slug of one of these.
+#[account]
+#[derive(InitSpace)]
+pub struct Genre {
+    #[max_len(MAX_GENRE_LENGTH)]
+    pub slug: String,
+    #[max_len(MAX_GENRE_DISPLAY_NAME_LENGTH)]
+    pub display_name: String,
+    pub tale_count: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+const GENRE_ACCOUNT_SPACE: usize = 8 + Genre::INIT_SPACE;
+
+impl Genre {
+    // Saturating: legacy tales may carry a genre that was only registered after they were
+    // created, so they were never counted in it.
+    fn release_tale(&mut self) {
+        self.tale_count = self.tale_count.saturating_sub(1);
     }
+}
 
-    pub fn update_tale(
-        ctx: Context<UpdateTale>,
-        new_title: String,
-        new_content_cid: String, // This argument will be checked
-        new_genre: String,
-        new_cover_image_cid: String,
-        new_thumbnail_cid: String,
-        new_status: u8,
-        new_candy_machine_address_option: Option<Pubkey>,
-        new_is_governance: bool,
-        new_is_early_access: bool,
-        new_is_real_world: bool,
-    ) -> Result<()> {
-        if new_title.len() > MAX_TITLE_LENGTH { return err!(AppError::TitleTooLong); }
-        if new_content_cid.len() > MAX_TALE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Error could also originate here
-        if new_genre.len() > MAX_GENRE_LENGTH { return err!(AppError::GenreTooLong); }
-        if new_cover_image_cid.len() > MAX_COVER_IMAGE_CID_LENGTH { return err!(AppError::CoverImageCidTooLong); }
-        if new_thumbnail_cid.len() > MAX_THUMBNAIL_CID_LENGTH { return err!(AppError::ThumbnailCidTooLong); }
-        if new_status > TaleStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
+// Singleton holding the platform admin and fee. Fees are paid into `treasury`, a data-less
+// PDA for lamports, or token accounts owned by it for SPL mints.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub treasury_bump: u8,
+    pub bump: u8,
+    #[max_len(MAX_MODERATORS)]
+    pub moderators: Vec<Pubkey>,
+    // Open reports that automatically hide visible content; 0 disables auto-hiding.
+    pub report_hide_threshold: u32,
+}
 
-        let tale = &mut ctx.accounts.tale_account;
-        tale.title = new_title;
-        tale.content_cid = new_content_cid;
-        tale.genre = new_genre;
-        tale.cover_image_cid = new_cover_image_cid;
-        tale.thumbnail_cid = new_thumbnail_cid;
-        tale.status = new_status;
-        tale.timestamp = Clock::get()?.unix_timestamp;
-        tale.candy_machine_address = new_candy_machine_address_option;
-        tale.is_governance_token_gated = new_is_governance;
-        tale.is_early_access_token_gated = new_is_early_access;
-        tale.is_real_world_asset_gated = new_is_real_world;
-        msg!("Tale updated: {}, Content CID: {}, Thumbnail: {}", tale.title, tale.content_cid, tale.thumbnail_cid);
-        Ok(())
+const PLATFORM_CONFIG_ACCOUNT_SPACE: usize = 8 + PlatformConfig::INIT_SPACE;
+
+impl PlatformConfig {
+    fn is_moderator(&self, wallet: &Pubkey) -> bool {
+        self.admin == *wallet || self.moderators.contains(wallet)
     }
+}
 
-    pub fn delete_tale(ctx: Context<DeleteTale>) -> Result<()> {
-        let tale = &ctx.accounts.tale_account;
-        if tale.status == TaleStatus::Published as u8 {
-            return err!(AppError::CannotDeletePublished);
-        }
-        msg!("Tale deleted: {}", tale.title);
+// --- Collaborator Role Bitflags ---
+pub const ROLE_EDIT_EPISODES: u8 = 1 << 0; // create, update and delete episodes
+pub const ROLE_PUBLISH: u8 = 1 << 1; // publish, schedule, archive and restore episodes
+pub const ROLE_MANAGE_COLLABORATORS: u8 = 1 << 2; // add, update and remove other collaborators
+const ALL_COLLABORATOR_ROLES: u8 = ROLE_EDIT_EPISODES | ROLE_PUBLISH | ROLE_MANAGE_COLLABORATORS;
+
+// --- Patch Structs ---
+// Every field is optional; `None` leaves the stored value untouched.
+// Status is not patchable: use the dedicated status instructions instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TalePatch {
+    pub title: Option<String>,
+    pub content_cid: Option<String>,
+    pub genre: Option<String>,
+    pub cover_image_cid: Option<String>,
+    pub thumbnail_cid: Option<String>,
+    pub candy_machine_address: Option<Option<Pubkey>>,
+    pub is_governance_token_gated: Option<bool>,
+    pub is_early_access_token_gated: Option<bool>,
+    pub is_real_world_asset_gated: Option<bool>,
+}
+
+impl TalePatch {
+    fn validate(&self) -> Result<()> {
+        if self.title.as_ref().is_some_and(|v| v.len() > MAX_TITLE_LENGTH) { return err!(AppError::TitleTooLong); }
+        if self.content_cid.as_ref().is_some_and(|v| v.len() > MAX_TALE_CONTENT_CID_LENGTH) { return err!(AppError::ContentCidTooLong); }
+        if self.genre.as_ref().is_some_and(|v| v.len() > MAX_GENRE_LENGTH) { return err!(AppError::GenreTooLong); }
+        if self.cover_image_cid.as_ref().is_some_and(|v| v.len() > MAX_COVER_IMAGE_CID_LENGTH) { return err!(AppError::CoverImageCidTooLong); }
+        if self.thumbnail_cid.as_ref().is_some_and(|v| v.len() > MAX_THUMBNAIL_CID_LENGTH) { return err!(AppError::ThumbnailCidTooLong); }
         Ok(())
     }
+}
 
-    // --- Episode Instructions ---
-    // (These remain unchanged from the previous version that included episode scheduling, thumbnail, and likes)
-    pub fn create_episode(
-        ctx: Context<CreateEpisode>,
-        episode_id_seed: String,
-        episode_name: String,
-        content_cid: String,
-        thumbnail_cid: String,
-        image_set_id: String,
-        order: u32,
-        initial_status: u8,
-        is_nft: bool,
-        candy_machine_id: String,
-        publish_at_time_option: Option<i64>,
-        unpublish_at_time_option: Option<i64>,
-    ) -> Result<()> {
-        if episode_id_seed.len() > MAX_EPISODE_ID_SEED_LENGTH { return err!(AppError::EpisodeIdSeedTooLong); }
-        if episode_name.len() > MAX_EPISODE_NAME_LENGTH { return err!(AppError::EpisodeNameTooLong); }
-        if content_cid.len() > MAX_EPISODE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Note: Episode content CID length
-        if thumbnail_cid.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH { return err!(AppError::EpisodeThumbnailCidTooLong); }
-        if image_set_id.len() > MAX_IMAGE_SET_ID_LENGTH { return err!(AppError::ImageSetIdTooLong); }
-        if candy_machine_id.len() > MAX_CANDY_MACHINE_ID_LENGTH { return err!(AppError::CandyMachineIdTooLong); }
-        if initial_status > EpisodeStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
-        if let (Some(publish_ts), Some(unpublish_ts)) = (publish_at_time_option, unpublish_at_time_option) {
-            if publish_ts >= unpublish_ts {
-                return err!(AppError::InvalidScheduleTimes);
-            }
-        }
+// `publish_at_time` / `unpublish_at_time` use `Some(None)` to clear the stored time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct EpisodePatch {
+    pub episode_name: Option<String>,
+    pub content_cid: Option<String>,
+    pub thumbnail_cid: Option<String>,
+    pub image_set_id: Option<String>,
+    pub order: Option<u32>,
+    pub is_nft: Option<bool>,
+    pub candy_machine_id: Option<String>,
+    pub publish_at_time: Option<Option<i64>>,
+    pub unpublish_at_time: Option<Option<i64>>,
+}
 
-        let episode = &mut ctx.accounts.episode_account;
-        let tale_account = &ctx.accounts.parent_tale_account;
-        episode.author = *ctx.accounts.author.key;
-        episode.parent_tale = tale_account.key();
-        episode.episode_id_seed = episode_id_seed;
-        episode.episode_name = episode_name;
-        episode.content_cid = content_cid;
-        episode.thumbnail_cid = thumbnail_cid;
-        episode.image_set_id = image_set_id;
-        episode.order = order;
-        episode.status = initial_status;
-        episode.is_nft = is_nft;
-        episode.candy_machine_id = if is_nft { candy_machine_id } else { "".to_string() };
-        episode.timestamp = Clock::get()?.unix_timestamp;
-        episode.bump = ctx.bumps.episode_account;
-        episode.publish_at_time = publish_at_time_option;
-        episode.unpublish_at_time = unpublish_at_time_option;
-        episode.like_count = 0;
-        msg!("Episode created: '{}'", episode.episode_name);
+impl EpisodePatch {
+    fn validate(&self) -> Result<()> {
+        if self.episode_name.as_ref().is_some_and(|v| v.len() > MAX_EPISODE_NAME_LENGTH) { return err!(AppError::EpisodeNameTooLong); }
+        if self.content_cid.as_ref().is_some_and(|v| v.len() > MAX_EPISODE_CONTENT_CID_LENGTH) { return err!(AppError::ContentCidTooLong); }
+        if self.thumbnail_cid.as_ref().is_some_and(|v| v.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH) { return err!(AppError::EpisodeThumbnailCidTooLong); }
+        if self.image_set_id.as_ref().is_some_and(|v| v.len() > MAX_IMAGE_SET_ID_LENGTH) { return err!(AppError::ImageSetIdTooLong); }
+        if self.candy_machine_id.as_ref().is_some_and(|v| v.len() > MAX_CANDY_MACHINE_ID_LENGTH) { return err!(AppError::CandyMachineIdTooLong); }
         Ok(())
     }
+}
 
-    pub fn update_episode(
-        ctx: Context<UpdateEpisode>,
-        new_episode_name: String,
-        new_content_cid: String,
-        new_thumbnail_cid: String,
-        new_image_set_id: String,
-        new_order: u32,
-        new_status: u8,
-        new_is_nft: bool,
-        new_candy_machine_id: String,
-        new_publish_at_time_option: Option<i64>,
-        new_unpublish_at_time_option: Option<i64>,
+// --- Events ---
+// Every mutating instruction emits one of these so indexers can decode them from the IDL
+// instead of parsing log strings.
+#[event]
+pub struct TaleCreated {
+    pub tale: Pubkey,
+    pub author: Pubkey,
+    pub tale_id: String,
+    pub title: String,
+    pub content_cid: String,
+    pub genre: String,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+// Carries the tale's editable fields after the update, for both full and patch updates.
+#[event]
+pub struct TaleUpdated {
+    pub tale: Pubkey,
+    pub editor: Pubkey,
+    pub title: String,
+    pub content_cid: String,
+    pub genre: String,
+    pub cover_image_cid: String,
+    pub thumbnail_cid: String,
+    pub candy_machine_address: Option<Pubkey>,
+    pub is_governance_token_gated: bool,
+    pub is_early_access_token_gated: bool,
+    pub is_real_world_asset_gated: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleDeleted {
+    pub tale: Pubkey,
+    pub author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleTransferProposed {
+    pub tale: Pubkey,
+    pub author: Pubkey,
+    pub pending_author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleTransferCancelled {
+    pub tale: Pubkey,
+    pub author: Pubkey,
+    pub pending_author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleTransferred {
+    pub tale: Pubkey,
+    pub previous_author: Pubkey,
+    pub new_author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForkPolicyUpdated {
+    pub tale: Pubkey,
+    pub fork_policy: u8,
+    pub fork_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForkApproved {
+    pub upstream: Pubkey,
+    pub forker: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForkApprovalRevoked {
+    pub upstream: Pubkey,
+    pub forker: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleForked {
+    pub tale: Pubkey,
+    pub upstream_tale: Pubkey,
+    pub author: Pubkey,
+    pub upstream_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TalePublished {
+    pub tale: Pubkey,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleArchived {
+    pub tale: Pubkey,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleRestored {
+    pub tale: Pubkey,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleGatesUpdated {
+    pub tale: Pubkey,
+    pub governance_gate: Option<TokenGate>,
+    pub early_access_gate: Option<TokenGate>,
+    pub real_world_asset_gate: Option<TokenGate>,
+    pub collection_gates: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadAccessGranted {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub access_pass: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollaboratorAdded {
+    pub tale: Pubkey,
+    pub wallet: Pubkey,
+    pub roles: u8,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollaboratorUpdated {
+    pub tale: Pubkey,
+    pub wallet: Pubkey,
+    pub previous_roles: u8,
+    pub roles: u8,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollaboratorRemoved {
+    pub tale: Pubkey,
+    pub wallet: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeCreated {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub author: Pubkey,
+    pub episode_id_seed: String,
+    pub episode_name: String,
+    pub content_cid: String,
+    pub order: u32,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+// Carries the episode's editable fields after the update, for both full and patch updates.
+#[event]
+pub struct EpisodeUpdated {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub editor: Pubkey,
+    pub episode_name: String,
+    pub content_cid: String,
+    pub thumbnail_cid: String,
+    pub image_set_id: String,
+    pub order: u32,
+    pub is_nft: bool,
+    pub candy_machine_id: String,
+    pub publish_at_time: Option<i64>,
+    pub unpublish_at_time: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonCreated {
+    pub season: Pubkey,
+    pub tale: Pubkey,
+    pub index: u32,
+    pub title: String,
+    pub created_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonUpdated {
+    pub season: Pubkey,
+    pub tale: Pubkey,
+    pub title: String,
+    pub cover_image_cid: String,
+    pub status: u8,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonClosed {
+    pub season: Pubkey,
+    pub tale: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeSeasonChanged {
+    pub episode: Pubkey,
+    pub previous_season: Option<Pubkey>,
+    pub new_season: Option<Pubkey>,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeProposalSubmitted {
+    pub proposal: Pubkey,
+    pub tale: Pubkey,
+    pub index: u32,
+    pub proposer: Pubkey,
+    pub episode_name: String,
+    pub content_cid: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeProposalAccepted {
+    pub proposal: Pubkey,
+    pub tale: Pubkey,
+    pub episode: Pubkey,
+    pub proposer: Pubkey,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeProposalRejected {
+    pub proposal: Pubkey,
+    pub tale: Pubkey,
+    pub proposer: Pubkey,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeBranchAttached {
+    pub episode: Pubkey,
+    pub next_episode: Pubkey,
+    pub label: String,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeBranchDetached {
+    pub episode: Pubkey,
+    pub next_episode: Pubkey,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeEndingSet {
+    pub episode: Pubkey,
+    pub is_ending: bool,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeRevisionRecorded {
+    pub episode: Pubkey,
+    pub revision: u32,
+    pub previous_content_cid: String,
+    pub previous_thumbnail_cid: String,
+    pub editor: Pubkey,
+    pub timestamp: i64,
+}
+
+// For the episode status events, `changed_by` is `None` when the schedule crank made the change.
+#[event]
+pub struct EpisodePublished {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub changed_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeScheduled {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub changed_by: Option<Pubkey>,
+    pub publish_at_time: Option<i64>,
+    pub unpublish_at_time: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeUnscheduled {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub changed_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeArchived {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub changed_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeRestored {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub changed_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeDeleted {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub deleted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodePriceUpdated {
+    pub episode: Pubkey,
+    pub price: u64,
+    pub price_mint: Option<Pubkey>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodePurchased {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub reader: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub upstream_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionPlanUpdated {
+    pub tale: Pubkey,
+    pub price: u64,
+    pub period_seconds: i64,
+    pub mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+// Emitted by both `subscribe` and `renew_subscription`.
+#[event]
+pub struct SubscriptionPaid {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub upstream_amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ModeratorAdded {
+    pub moderator: Pubkey,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ModeratorRemoved {
+    pub moderator: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleModerated {
+    pub tale: Pubkey,
+    pub previous_state: u8,
+    pub new_state: u8,
+    pub reason: u16,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeModerated {
+    pub episode: Pubkey,
+    pub parent_tale: Pubkey,
+    pub previous_state: u8,
+    pub new_state: u8,
+    pub reason: u16,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReportHideThresholdUpdated {
+    pub report_hide_threshold: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContentReported {
+    pub report: Pubkey,
+    pub target: Pubkey,
+    pub tale: Pubkey,
+    pub reporter: Pubkey,
+    pub reason: u8,
+    pub evidence_cid: String,
+    pub report_count: u32,
+    pub timestamp: i64,
+}
+
+// `status` is the report's final `ReportStatus`.
+#[event]
+pub struct ReportClosed {
+    pub report: Pubkey,
+    pub target: Pubkey,
+    pub tale: Pubkey,
+    pub reporter: Pubkey,
+    pub reason: u8,
+    pub status: u8,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GenreCreated {
+    pub genre: Pubkey,
+    pub slug: String,
+    pub display_name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GenreUpdated {
+    pub genre: Pubkey,
+    pub slug: String,
+    pub display_name: String,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TipSplitsUpdated {
+    pub tale: Pubkey,
+    pub tip_splits: Vec<TipSplit>,
+    pub timestamp: i64,
+}
+
+// `mint` is `None` for lamport tips. `author_amount` excludes the platform fee and collaborator splits.
+#[event]
+pub struct TipSent {
+    pub tale: Pubkey,
+    pub episode: Option<Pubkey>,
+    pub tipper: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub upstream_amount: u64,
+    pub author_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentPosted {
+    pub comment: Pubkey,
+    pub episode: Pubkey,
+    pub index: u32,
+    pub author: Pubkey,
+    pub parent: Option<u32>,
+    pub body: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentEdited {
+    pub comment: Pubkey,
+    pub episode: Pubkey,
+    pub index: u32,
+    pub body: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentDeleted {
+    pub comment: Pubkey,
+    pub episode: Pubkey,
+    pub index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentVisibilityChanged {
+    pub comment: Pubkey,
+    pub episode: Pubkey,
+    pub index: u32,
+    pub is_hidden: bool,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReaderProgressUpdated {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub episode: Pubkey,
+    pub order: u32,
+    pub episodes_read: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleCompleted {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BookmarkAdded {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub episode: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BookmarkRemoved {
+    pub tale: Pubkey,
+    pub reader: Pubkey,
+    pub episode: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProfileCreated {
+    pub profile: Pubkey,
+    pub wallet: Pubkey,
+    pub display_name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProfileUpdated {
+    pub profile: Pubkey,
+    pub wallet: Pubkey,
+    pub display_name: String,
+    pub bio_cid: String,
+    pub avatar_cid: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HandleClaimed {
+    pub handle: String,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HandleReleased {
+    pub handle: String,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HandleTransferred {
+    pub handle: String,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleMigrated {
+    pub tale: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeMigrated {
+    pub episode: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleLiked {
+    pub tale: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaleUnliked {
+    pub tale: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeLiked {
+    pub episode: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpisodeUnliked {
+    pub episode: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+// --- Enums for Status ---
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TaleStatus { Draft = 0, Published = 1, Archived = 2 }
+
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum EpisodeStatus {
+    Draft = 0,
+    Published = 1,
+    Scheduled = 2,
+    Archived = 3,
+}
+
+// --- Token Gate Bitflags ---
+pub const GATE_GOVERNANCE: u8 = 1 << 0;
+pub const GATE_EARLY_ACCESS: u8 = 1 << 1;
+pub const GATE_REAL_WORLD_ASSET: u8 = 1 << 2;
+
+// The collection `mint` is a verified member of, read from its Token Metadata account.
+fn verified_collection(metadata_info: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>> {
+    let (address, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    if metadata_info.key() != address || metadata_info.owner != &TOKEN_METADATA_PROGRAM_ID {
+        return err!(AppError::InvalidGateMetadata);
+    }
+    let metadata = MetadataPrefix::deserialize(&mut &metadata_info.try_borrow_data()?[..])
+        .map_err(|_| AppError::InvalidGateMetadata)?;
+    Ok(metadata.collection.filter(|collection| collection.verified).map(|collection| collection.key))
+}
+
+// Set only by platform moderators. Hidden content stays editable by its author but should not
+// be listed by clients; Removed content is frozen for its author except for deletion.
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ModerationState { Visible = 0, Hidden = 1, Removed = 2 }
+
+impl ModerationState {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ModerationState::Visible),
+            1 => Ok(ModerationState::Hidden),
+            2 => Ok(ModerationState::Removed),
+            _ => err!(AppError::InvalidModerationState),
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ForkPolicy {
+    Disallowed = 0,
+    Free = 1,
+    RequiresApproval = 2,
+    RevenueShare = 3,
+}
+
+impl ForkPolicy {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ForkPolicy::Disallowed),
+            1 => Ok(ForkPolicy::Free),
+            2 => Ok(ForkPolicy::RequiresApproval),
+            3 => Ok(ForkPolicy::RevenueShare),
+            _ => err!(AppError::InvalidForkPolicy),
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ReportReason {
+    Spam = 0,
+    Harassment = 1,
+    Infringement = 2,
+    IllegalContent = 3,
+    Other = 4,
+}
+
+impl ReportReason {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ReportReason::Spam),
+            1 => Ok(ReportReason::Harassment),
+            2 => Ok(ReportReason::Infringement),
+            3 => Ok(ReportReason::IllegalContent),
+            4 => Ok(ReportReason::Other),
+            _ => err!(AppError::InvalidReportReason),
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ReportStatus { Open = 0, Resolved = 1, Dismissed = 2 }
+
+impl TaleStatus {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(TaleStatus::Draft),
+            1 => Ok(TaleStatus::Published),
+            2 => Ok(TaleStatus::Archived),
+            _ => err!(AppError::InvalidStatus),
+        }
+    }
+
+    // Draft -> Published | Archived, Published -> Archived, Archived -> Draft.
+    // Staying in the same status is always allowed so full updates can resend it.
+    fn can_transition_to(&self, next: &TaleStatus) -> bool {
+        self == next
+            || matches!(
+                (self, next),
+                (TaleStatus::Draft, TaleStatus::Published)
+                    | (TaleStatus::Draft, TaleStatus::Archived)
+                    | (TaleStatus::Published, TaleStatus::Archived)
+                    | (TaleStatus::Archived, TaleStatus::Draft)
+            )
+    }
+}
+
+impl EpisodeStatus {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(EpisodeStatus::Draft),
+            1 => Ok(EpisodeStatus::Published),
+            2 => Ok(EpisodeStatus::Scheduled),
+            3 => Ok(EpisodeStatus::Archived),
+            _ => err!(AppError::InvalidStatus),
+        }
+    }
+
+    // Draft -> Scheduled | Published | Archived, Scheduled -> Draft | Published | Archived,
+    // Published -> Archived, Archived -> Draft. Published episodes never fall back to Draft.
+    fn can_transition_to(&self, next: &EpisodeStatus) -> bool {
+        self == next
+            || matches!(
+                (self, next),
+                (EpisodeStatus::Draft, EpisodeStatus::Scheduled)
+                    | (EpisodeStatus::Draft, EpisodeStatus::Published)
+                    | (EpisodeStatus::Draft, EpisodeStatus::Archived)
+                    | (EpisodeStatus::Scheduled, EpisodeStatus::Draft)
+                    | (EpisodeStatus::Scheduled, EpisodeStatus::Published)
+                    | (EpisodeStatus::Scheduled, EpisodeStatus::Archived)
+                    | (EpisodeStatus::Published, EpisodeStatus::Archived)
+                    | (EpisodeStatus::Archived, EpisodeStatus::Draft)
+            )
+    }
+}
+
+fn transition_tale(tale: &mut Tale, next: TaleStatus) -> Result<()> {
+    let current = TaleStatus::from_u8(tale.status)?;
+    if !current.can_transition_to(&next) { return err!(AppError::InvalidTaleStatusTransition); }
+    tale.status = next as u8;
+    Ok(())
+}
+
+fn transition_episode(episode: &mut Episode, next: EpisodeStatus) -> Result<()> {
+    let current = EpisodeStatus::from_u8(episode.status)?;
+    if !current.can_transition_to(&next) { return err!(AppError::InvalidEpisodeStatusTransition); }
+    episode.status = next as u8;
+    Ok(())
+}
+
+// Emits the event matching the tale's new status, if it changed.
+fn emit_tale_status_event(tale: &Account<Tale>, previous_status: u8, changed_by: Pubkey, timestamp: i64) {
+    if tale.status == previous_status { return; }
+    let tale_key = tale.key();
+    match TaleStatus::from_u8(tale.status) {
+        Ok(TaleStatus::Published) => emit!(TalePublished { tale: tale_key, changed_by, timestamp }),
+        Ok(TaleStatus::Archived) => emit!(TaleArchived { tale: tale_key, changed_by, timestamp }),
+        Ok(TaleStatus::Draft) => emit!(TaleRestored { tale: tale_key, changed_by, timestamp }),
+        Err(_) => {}
+    }
+}
+
+// Emits the event matching the episode's new status, if it changed.
+fn emit_episode_status_event(episode: &Account<Episode>, previous_status: u8, changed_by: Option<Pubkey>, timestamp: i64) {
+    if episode.status == previous_status { return; }
+    let (episode_key, parent_tale) = (episode.key(), episode.parent_tale);
+    match EpisodeStatus::from_u8(episode.status) {
+        Ok(EpisodeStatus::Published) => emit!(EpisodePublished { episode: episode_key, parent_tale, changed_by, timestamp }),
+        Ok(EpisodeStatus::Scheduled) => emit!(EpisodeScheduled {
+            episode: episode_key,
+            parent_tale,
+            changed_by,
+            publish_at_time: episode.publish_at_time,
+            unpublish_at_time: episode.unpublish_at_time,
+            timestamp,
+        }),
+        Ok(EpisodeStatus::Archived) => emit!(EpisodeArchived { episode: episode_key, parent_tale, changed_by, timestamp }),
+        Ok(EpisodeStatus::Draft) if previous_status == EpisodeStatus::Scheduled as u8 => {
+            emit!(EpisodeUnscheduled { episode: episode_key, parent_tale, changed_by, timestamp })
+        }
+        Ok(EpisodeStatus::Draft) => emit!(EpisodeRestored { episode: episode_key, parent_tale, changed_by, timestamp }),
+        Err(_) => {}
+    }
+}
+
+fn emit_tale_updated(tale: &Account<Tale>, editor: Pubkey) {
+    emit!(TaleUpdated {
+        tale: tale.key(),
+        editor,
+        title: tale.title.clone(),
+        content_cid: tale.content_cid.clone(),
+        genre: tale.genre.clone(),
+        cover_image_cid: tale.cover_image_cid.clone(),
+        thumbnail_cid: tale.thumbnail_cid.clone(),
+        candy_machine_address: tale.candy_machine_address,
+        is_governance_token_gated: tale.is_governance_token_gated,
+        is_early_access_token_gated: tale.is_early_access_token_gated,
+        is_real_world_asset_gated: tale.is_real_world_asset_gated,
+        timestamp: tale.timestamp,
+    });
+}
+
+fn emit_episode_updated(episode: &Account<Episode>, editor: Pubkey) {
+    emit!(EpisodeUpdated {
+        episode: episode.key(),
+        parent_tale: episode.parent_tale,
+        editor,
+        episode_name: episode.episode_name.clone(),
+        content_cid: episode.content_cid.clone(),
+        thumbnail_cid: episode.thumbnail_cid.clone(),
+        image_set_id: episode.image_set_id.clone(),
+        order: episode.order,
+        is_nft: episode.is_nft,
+        candy_machine_id: episode.candy_machine_id.clone(),
+        publish_at_time: episode.publish_at_time,
+        unpublish_at_time: episode.unpublish_at_time,
+        timestamp: episode.timestamp,
+    });
+}
+
+// The tale author may do anything; anyone else needs a TaleCollaborator record
+// for this tale holding every bit in `role`.
+fn authorize_tale_role(
+    tale: &Account<Tale>,
+    signer: &Pubkey,
+    collaborator: Option<&TaleCollaborator>,
+    role: u8,
+) -> Result<()> {
+    if tale.author == *signer { return Ok(()); }
+    match collaborator {
+        Some(c) if c.tale == tale.key()
+            && c.wallet == *signer
+            && c.generation == tale.collaborator_generation
+            && c.roles & role == role => Ok(()),
+        _ => err!(AppError::Unauthorized),
+    }
+}
+
+// Non-author managers may not hand out, or take away, the manage collaborators role.
+fn authorize_collaborator_change(
+    tale: &Account<Tale>,
+    manager: &Pubkey,
+    manager_collaborator: Option<&TaleCollaborator>,
+    roles_touched: u8,
+) -> Result<()> {
+    authorize_tale_role(tale, manager, manager_collaborator, ROLE_MANAGE_COLLABORATORS)?;
+    if tale.author != *manager && roles_touched & ROLE_MANAGE_COLLABORATORS != 0 {
+        return err!(AppError::CannotManageCollaboratorManagers);
+    }
+    Ok(())
+}
+
+// Same as Anchor's `close` constraint, for accounts that are only known at runtime
+// (e.g. passed through `remaining_accounts`).
+fn close_account<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let destination_starting_lamports = destination.lamports();
+    **destination.lamports.borrow_mut() = destination_starting_lamports
+        .checked_add(info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **info.lamports.borrow_mut() = 0;
+    info.assign(&System::id());
+    info.realloc(0, false).map_err(Into::into)
+}
+
+// Reallocs a program-owned account up to `space`, topping up rent from `payer`.
+// Accounts that are already large enough are left as they are.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if info.data_len() >= space { return Ok(()); }
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(system_program.clone(), system_program::Transfer { from: payer.clone(), to: info.clone() }),
+            required_lamports,
+        )?;
+    }
+    info.realloc(space, true).map_err(Into::into)
+}
+
+// Decodes a pre-versioning account as `T` with every field after `like_count` zeroed (`None`,
+// empty or 0), `version` included, instead of reading the stale bytes that follow it. Zero is
+// the initial value of each of those fields, so nothing needs resetting afterwards.
+fn decode_legacy_account<L, T>(info: &AccountInfo, space: usize) -> Result<T>
+where
+    L: AnchorSerialize + AnchorDeserialize,
+    T: AccountDeserialize,
+{
+    let data = info.try_borrow_data()?;
+    let legacy = L::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+    let mut bytes = data[..8].to_vec();
+    legacy.serialize(&mut bytes).map_err(|_| ErrorCode::AccountDidNotSerialize)?;
+    bytes.resize(space, 0);
+    T::try_deserialize(&mut &bytes[..])
+}
+
+// Rewrites the whole account so no stale bytes from the previous layout survive past the new data.
+fn rewrite_account<T: AccountSerialize>(info: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    data.fill(0);
+    value.try_serialize(&mut &mut data[..])
+}
+
+fn validate_genre_slug(slug: &str) -> Result<()> {
+    let valid_chars = slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if !valid_chars || slug.is_empty() || slug.len() > MAX_GENRE_LENGTH { return err!(AppError::InvalidGenreSlug); }
+    Ok(())
+}
+
+// Address of a tale created by `create_tale`, namespaced by its creator.
+pub fn find_tale_address(creator: &Pubkey, tale_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tale", creator.as_ref(), tale_id.as_bytes()], &crate::ID)
+}
+
+// Address of a tale created before version 10, when tale IDs were global. Clients resolving an
+// (author, tale ID) pair try `find_tale_address` first and fall back to this one, checking `author`.
+pub fn find_legacy_tale_address(tale_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tale", tale_id.as_bytes()], &crate::ID)
+}
+
+// Decrements the tale counter of the genre a tale is leaving. `genre_info` must sit at the
+// genre PDA for `slug`; it may be uninitialized for free-text genres from before the registry.
+fn release_genre(slug: &str, genre_info: Option<&AccountInfo>) -> Result<()> {
+    let genre_info = genre_info.ok_or(AppError::MissingGenreAccount)?;
+    let (address, _) = Pubkey::find_program_address(&[b"genre", slug.as_bytes()], &crate::ID);
+    if genre_info.key() != address { return err!(AppError::GenreAccountMismatch); }
+    if genre_info.owner != &crate::ID || genre_info.data_is_empty() { return Ok(()); }
+    if !genre_info.is_writable { return err!(ErrorCode::ConstraintMut); }
+
+    let mut genre = Genre::try_deserialize(&mut &genre_info.try_borrow_data()?[..])?;
+    genre.release_tale();
+    genre.try_serialize(&mut &mut genre_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+// Handles are stored and seeded in lowercase; clients must normalize before deriving the PDA.
+fn validate_handle(handle: &str) -> Result<()> {
+    let valid_chars = handle.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    if !valid_chars || handle.len() < MIN_HANDLE_LENGTH || handle.len() > MAX_HANDLE_LENGTH {
+        return err!(AppError::InvalidHandle);
+    }
+    Ok(())
+}
+
+fn validate_profile_fields(display_name: &str, bio_cid: &str, avatar_cid: &str) -> Result<()> {
+    if display_name.len() > MAX_DISPLAY_NAME_LENGTH { return err!(AppError::DisplayNameTooLong); }
+    if bio_cid.len() > MAX_BIO_CID_LENGTH { return err!(AppError::BioCidTooLong); }
+    if avatar_cid.len() > MAX_AVATAR_CID_LENGTH { return err!(AppError::AvatarCidTooLong); }
+    Ok(())
+}
+
+fn validate_comment_body(body: &str) -> Result<()> {
+    if body.is_empty() { return err!(AppError::EmptyComment); }
+    if body.len() > MAX_COMMENT_BODY_LENGTH { return err!(AppError::CommentTooLong); }
+    Ok(())
+}
+
+fn bps_share(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128) * (bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(share).map_err(|_| error!(AppError::ArithmeticOverflow))
+}
+
+// The SPL side of a payment. Absent for lamport payments.
+struct SplPayment<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    payer_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+// The cut of a fork's revenue owed to the upstream tale's current author.
+struct UpstreamShare<'a, 'info> {
+    author: Pubkey,
+    share_bps: u16,
+    receiver: &'a AccountInfo<'info>,
+}
+
+// Resolves who is owed `tale.upstream_share_bps`. `upstream_tale` must be the fork's upstream;
+// if that tale has since been deleted nothing is owed.
+fn upstream_share<'a, 'info>(
+    tale: &Tale,
+    upstream_tale: Option<&AccountInfo<'info>>,
+    upstream_receiver: Option<&'a AccountInfo<'info>>,
+) -> Result<Option<UpstreamShare<'a, 'info>>> {
+    let Some(upstream_key) = tale.upstream_tale else { return Ok(None) };
+    if tale.upstream_share_bps == 0 { return Ok(None); }
+    let upstream_info = upstream_tale.ok_or(AppError::MissingUpstreamTale)?;
+    if upstream_info.key() != upstream_key { return err!(AppError::UpstreamTaleMismatch); }
+    if upstream_info.owner != &crate::ID || upstream_info.data_is_empty() { return Ok(None); }
+
+    let author = read_tale_author(upstream_info)?;
+    let receiver = upstream_receiver.ok_or(AppError::MissingPaymentRecipient)?;
+    Ok(Some(UpstreamShare { author, share_bps: tale.upstream_share_bps, receiver }))
+}
+
+// `Tale.author` comes first in every layout, so it can be read from a tale that has not been
+// migrated to the current version, which a full `Tale` decode would reject.
+fn read_tale_author(info: &AccountInfo) -> Result<Pubkey> {
+    let data = info.try_borrow_data()?;
+    if data.get(..8) != Some(Tale::DISCRIMINATOR) { return err!(ErrorCode::AccountDiscriminatorMismatch); }
+    let author: [u8; 32] = data
+        .get(8..40)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(Pubkey::new_from_array(author))
+}
+
+// Funds moving out of the payer's wallet, in lamports or in a single SPL mint.
+struct PaymentSource<'a, 'info> {
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    spl: Option<SplPayment<'a, 'info>>,
+}
+
+impl<'a, 'info> PaymentSource<'a, 'info> {
+    fn new(
+        payer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        payer_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let spl = match (mint, payer_token_account, token_program) {
+            (None, None, None) => None,
+            (Some(mint), Some(payer_token_account), Some(token_program)) => {
+                if payer_token_account.mint != mint.key() || payer_token_account.owner != payer.key() {
+                    return err!(AppError::InvalidPaymentRecipient);
+                }
+                Some(SplPayment { mint, payer_token_account, token_program })
+            }
+            _ => return err!(AppError::IncompleteTokenAccounts),
+        };
+        Ok(Self { payer: payer.to_account_info(), system_program: system_program.to_account_info(), spl })
+    }
+
+    fn mint(&self) -> Option<Pubkey> {
+        self.spl.as_ref().map(|spl| spl.mint.key())
+    }
+
+    // Sends the platform fee on `amount` to the treasury, the upstream share of the remainder to
+    // the upstream author, and the rest to `author`. Returns the fee and the upstream amount.
+    fn pay_with_platform_fee(
+        &self,
+        config: &PlatformConfig,
+        treasury_receiver: &AccountInfo<'info>,
+        author_receiver: &AccountInfo<'info>,
+        author: &Pubkey,
+        upstream: Option<&UpstreamShare<'_, 'info>>,
+        amount: u64,
+    ) -> Result<(u64, u64)> {
+        let platform_fee = bps_share(amount, config.fee_bps)?;
+        self.pay(treasury_receiver, &config.treasury, platform_fee)?;
+        let upstream_amount = self.pay_upstream(upstream, amount - platform_fee)?;
+        self.pay(author_receiver, author, amount - platform_fee - upstream_amount)?;
+        Ok((platform_fee, upstream_amount))
+    }
+
+    fn pay_upstream(&self, upstream: Option<&UpstreamShare<'_, 'info>>, amount: u64) -> Result<u64> {
+        let Some(upstream) = upstream else { return Ok(0) };
+        let share = bps_share(amount, upstream.share_bps)?;
+        self.pay(upstream.receiver, &upstream.author, share)?;
+        Ok(share)
+    }
+
+    // Sends `amount` to `recipient`, which must be `beneficiary` itself for lamports,
+    // or a token account of the payment mint owned by `beneficiary` for SPL.
+    fn pay(&self, recipient: &AccountInfo<'info>, beneficiary: &Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 { return Ok(()); }
+        match &self.spl {
+            None => {
+                if recipient.key() != *beneficiary { return err!(AppError::InvalidPaymentRecipient); }
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.clone(),
+                        system_program::Transfer { from: self.payer.clone(), to: recipient.clone() },
+                    ),
+                    amount,
+                )
+            }
+            Some(spl) => {
+                if recipient.owner != spl.token_program.key { return err!(AppError::InvalidPaymentRecipient); }
+                let recipient_token_account = TokenAccount::try_deserialize(&mut &recipient.try_borrow_data()?[..])?;
+                if recipient_token_account.owner != *beneficiary || recipient_token_account.mint != spl.mint.key() {
+                    return err!(AppError::InvalidPaymentRecipient);
+                }
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        spl.token_program.to_account_info(),
+                        TransferChecked {
+                            from: spl.payer_token_account.to_account_info(),
+                            mint: spl.mint.to_account_info(),
+                            to: recipient.clone(),
+                            authority: self.payer.clone(),
+                        },
+                    ),
+                    amount,
+                    spl.mint.decimals,
+                )
+            }
+        }
+    }
+}
+
+// Moves an episode along its schedule according to `now`:
+// Scheduled -> Published once `publish_at_time` has passed, then
+// Published -> Archived once `unpublish_at_time` has passed.
+// Returns true if the status changed.
+fn apply_episode_schedule(episode: &mut Episode, now: i64) -> bool {
+    let previous_status = episode.status;
+    if episode.status == EpisodeStatus::Scheduled as u8 {
+        if let Some(publish_ts) = episode.publish_at_time {
+            if now >= publish_ts {
+                episode.status = EpisodeStatus::Published as u8;
+            }
+        }
+    }
+    if episode.status == EpisodeStatus::Published as u8 {
+        if let Some(unpublish_ts) = episode.unpublish_at_time {
+            if now >= unpublish_ts {
+                episode.status = EpisodeStatus::Archived as u8;
+            }
+        }
+    }
+    episode.status != previous_status
+}
+
+// Checks the schedule an episode is about to be stored with. A scheduled episode needs a publish
+// time, and a publish time being set now must lie in the future: an overdue one would be published
+// by the next crank without going through the status transitions. A stored time that is kept may
+// already have passed while the episode waits for the crank.
+fn validate_episode_schedule(
+    status: u8,
+    publish_at_time: Option<i64>,
+    unpublish_at_time: Option<i64>,
+    publish_time_changed: bool,
+) -> Result<()> {
+    if status == EpisodeStatus::Scheduled as u8 {
+        let publish_ts = publish_at_time.ok_or(AppError::MissingPublishTime)?;
+        if publish_time_changed && publish_ts <= Clock::get()?.unix_timestamp { return err!(AppError::PublishTimeInPast); }
+    }
+    if let (Some(publish_ts), Some(unpublish_ts)) = (publish_at_time, unpublish_at_time) {
+        if publish_ts >= unpublish_ts { return err!(AppError::InvalidScheduleTimes); }
+    }
+    Ok(())
+}
+
+#[program]
+pub mod tale_story {
+    use super::*;
+
+    pub fn create_tale(
+        ctx: Context<CreateTale>,
+        tale_id: String,
+        title: String,
+        content_cid: String, // This argument will be checked against MAX_TALE_CONTENT_CID_LENGTH
+        genre: String,
+        cover_image_cid: String,
+        thumbnail_cid: String,
+        initial_status: u8,
+        candy_machine_address_option: Option<Pubkey>,
+        is_governance: bool,
+        is_early_access: bool,
+        is_real_world: bool,
+    ) -> Result<()> {
+        let bump = ctx.bumps.tale_account;
+        ctx.accounts.init_tale(bump, NewTale {
+            tale_id,
+            title,
+            content_cid,
+            genre,
+            cover_image_cid,
+            thumbnail_cid,
+            initial_status,
+            candy_machine_address_option,
+            is_governance,
+            is_early_access,
+            is_real_world,
+        })
+    }
+
+    pub fn update_tale(
+        ctx: Context<UpdateTale>,
+        new_title: String,
+        new_content_cid: String, // This argument will be checked
+        new_genre: String,
+        new_cover_image_cid: String,
+        new_thumbnail_cid: String,
+        new_status: u8,
+        new_candy_machine_address_option: Option<Pubkey>,
+        new_is_governance: bool,
+        new_is_early_access: bool,
+        new_is_real_world: bool,
+    ) -> Result<()> {
+        if new_title.len() > MAX_TITLE_LENGTH { return err!(AppError::TitleTooLong); }
+        if new_content_cid.len() > MAX_TALE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Error could also originate here
+        if new_genre.len() > MAX_GENRE_LENGTH { return err!(AppError::GenreTooLong); }
+        if new_cover_image_cid.len() > MAX_COVER_IMAGE_CID_LENGTH { return err!(AppError::CoverImageCidTooLong); }
+        if new_thumbnail_cid.len() > MAX_THUMBNAIL_CID_LENGTH { return err!(AppError::ThumbnailCidTooLong); }
+        let new_status = TaleStatus::from_u8(new_status)?;
+        ctx.accounts.change_genre(new_genre)?;
+
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_status = tale.status;
+        transition_tale(tale, new_status)?;
+        tale.title = new_title;
+        tale.content_cid = new_content_cid;
+        tale.cover_image_cid = new_cover_image_cid;
+        tale.thumbnail_cid = new_thumbnail_cid;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        tale.candy_machine_address = new_candy_machine_address_option;
+        tale.is_governance_token_gated = new_is_governance;
+        tale.is_early_access_token_gated = new_is_early_access;
+        tale.is_real_world_asset_gated = new_is_real_world;
+        emit_tale_updated(tale, ctx.accounts.author.key());
+        emit_tale_status_event(tale, previous_status, ctx.accounts.author.key(), tale.timestamp);
+        Ok(())
+    }
+
+    pub fn patch_tale(ctx: Context<UpdateTale>, patch: TalePatch) -> Result<()> {
+        patch.validate()?;
+        if let Some(genre) = patch.genre { ctx.accounts.change_genre(genre)?; }
+
+        let tale = &mut ctx.accounts.tale_account;
+        if let Some(title) = patch.title { tale.title = title; }
+        if let Some(content_cid) = patch.content_cid { tale.content_cid = content_cid; }
+        if let Some(cover_image_cid) = patch.cover_image_cid { tale.cover_image_cid = cover_image_cid; }
+        if let Some(thumbnail_cid) = patch.thumbnail_cid { tale.thumbnail_cid = thumbnail_cid; }
+        if let Some(candy_machine_address) = patch.candy_machine_address { tale.candy_machine_address = candy_machine_address; }
+        if let Some(is_governance) = patch.is_governance_token_gated { tale.is_governance_token_gated = is_governance; }
+        if let Some(is_early_access) = patch.is_early_access_token_gated { tale.is_early_access_token_gated = is_early_access; }
+        if let Some(is_real_world) = patch.is_real_world_asset_gated { tale.is_real_world_asset_gated = is_real_world; }
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        emit_tale_updated(tale, ctx.accounts.author.key());
+        Ok(())
+    }
+
+    pub fn delete_tale(ctx: Context<DeleteTale>) -> Result<()> {
+        let tale = &ctx.accounts.tale_account;
+        if tale.status == TaleStatus::Published as u8 {
+            return err!(AppError::CannotDeletePublished);
+        }
+        if tale.episode_count > 0 {
+            return err!(AppError::TaleHasEpisodes);
+        }
+        if tale.open_proposal_count > 0 { return err!(AppError::TaleHasProposals); }
+        if tale.season_count > 0 { return err!(AppError::TaleHasSeasons); }
+        if tale.has_uncounted_episodes() { return err!(AppError::TaleEpisodesUncounted); }
+        ctx.accounts.genre_account.release_tale();
+        emit!(TaleDeleted { tale: tale.key(), author: tale.author, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    // Closes the tale's remaining episodes, seasons and open proposals in batches. `remaining_accounts`
+    // holds (account, rent recipient) pairs where the recipient is the episode's `author`, the season's
+    // `created_by` or the proposal's `proposer`. Branches only link episodes of the same tale, so they
+    // go with the episodes. Once nothing is left the tale itself is closed in the same call, unless
+    // it is a legacy tale whose episodes cannot all be accounted for.
+    pub fn close_tale_cascade<'info>(ctx: Context<'_, '_, 'info, 'info, CloseTaleCascade<'info>>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        if tale.status == TaleStatus::Published as u8 {
+            return err!(AppError::CannotDeletePublished);
+        }
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return err!(AppError::InvalidCascadeAccounts);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        for pair in pairs {
+            let (record_info, recipient_info) = (&pair[0], &pair[1]);
+            if !record_info.is_writable || !recipient_info.is_writable { return err!(ErrorCode::ConstraintMut); }
+            let discriminator = record_info.try_borrow_data()?.get(..8).map(<[u8]>::to_vec).ok_or(AppError::InvalidCascadeAccounts)?;
+            if discriminator == Episode::DISCRIMINATOR {
+                let episode = Account::<Episode>::try_from(record_info)?;
+                if episode.parent_tale != tale.key() { return err!(AppError::EpisodeTaleMismatch); }
+                if episode.author != recipient_info.key() { return err!(AppError::Unauthorized); }
+                close_account(record_info, recipient_info)?;
+                tale.episode_count = tale.episode_count.checked_sub(1).ok_or(AppError::EpisodeCountUnderflow)?;
+                emit!(EpisodeDeleted { episode: record_info.key(), parent_tale: tale.key(), deleted_by: tale.author, timestamp: now });
+            } else if discriminator == Season::DISCRIMINATOR {
+                // Unlike `close_season`, a season may go before its episodes are moved out, but only
+                // once the tale's episodes are all closed so none is left pointing at it.
+                let season = Account::<Season>::try_from(record_info)?;
+                if season.tale != tale.key() { return err!(AppError::SeasonMismatch); }
+                if season.created_by != recipient_info.key() { return err!(AppError::Unauthorized); }
+                if season.episode_count > 0 && tale.episode_count > 0 { return err!(AppError::SeasonNotEmpty); }
+                close_account(record_info, recipient_info)?;
+                tale.season_count = tale.season_count.checked_sub(1).ok_or(AppError::SeasonCountUnderflow)?;
+                emit!(SeasonClosed { season: record_info.key(), tale: tale.key(), closed_by: tale.author, timestamp: now });
+            } else if discriminator == EpisodeProposal::DISCRIMINATOR {
+                let proposal = Account::<EpisodeProposal>::try_from(record_info)?;
+                if proposal.tale != tale.key() { return err!(AppError::EpisodeTaleMismatch); }
+                if proposal.proposer != recipient_info.key() { return err!(AppError::Unauthorized); }
+                close_account(record_info, recipient_info)?;
+                tale.open_proposal_count = tale.open_proposal_count.checked_sub(1).ok_or(AppError::ProposalCountUnderflow)?;
+                emit!(EpisodeProposalRejected {
+                    proposal: record_info.key(),
+                    tale: tale.key(),
+                    proposer: proposal.proposer,
+                    editor: tale.author,
+                    timestamp: now,
+                });
+            } else {
+                return err!(AppError::InvalidCascadeAccounts);
+            }
+        }
+
+        let emptied = tale.episode_count == 0 && tale.season_count == 0 && tale.open_proposal_count == 0;
+        if emptied && !tale.has_uncounted_episodes() {
+            ctx.accounts.genre_account.release_tale();
+            close_account(&tale.to_account_info(), &ctx.accounts.author.to_account_info())?;
+            emit!(TaleDeleted { tale: tale.key(), author: tale.author, timestamp: now });
+        }
+        Ok(())
+    }
+
+    // --- Tale Ownership Instructions ---
+    // Two-step so a tale can never be sent to a wallet that cannot sign. Proposing again replaces
+    // the pending author.
+    pub fn propose_tale_transfer(ctx: Context<TransferTale>, new_author: Pubkey) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        if new_author == tale.author { return err!(AppError::InvalidTransferRecipient); }
+        tale.pending_author = Some(new_author);
+        emit!(TaleTransferProposed {
+            tale: tale.key(),
+            author: tale.author,
+            pending_author: new_author,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_tale_transfer(ctx: Context<TransferTale>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        let pending_author = tale.pending_author.take().ok_or(AppError::NoPendingTransfer)?;
+        emit!(TaleTransferCancelled {
+            tale: tale.key(),
+            author: tale.author,
+            pending_author,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Existing episodes follow automatically since they are authorized through the tale.
+    // The previous author's profile link and tip splits do not carry over, and existing
+    // collaborators lose their roles until the new author (or their managers) update them.
+    pub fn accept_tale_transfer(ctx: Context<AcceptTaleTransfer>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_author = tale.author;
+        tale.author = ctx.accounts.new_author.key();
+        tale.pending_author = None;
+        tale.author_profile = None;
+        tale.tip_splits = Vec::new();
+        tale.collaborator_generation = tale.collaborator_generation.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        emit!(TaleTransferred {
+            tale: tale.key(),
+            previous_author,
+            new_author: tale.author,
+            timestamp: tale.timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Fork Instructions ---
+    pub fn set_fork_policy(ctx: Context<SetForkPolicy>, fork_policy: u8, fork_share_bps: u16) -> Result<()> {
+        let policy = ForkPolicy::from_u8(fork_policy)?;
+        let share_valid = match policy {
+            ForkPolicy::RevenueShare => fork_share_bps > 0 && fork_share_bps as u64 <= BPS_DENOMINATOR,
+            _ => fork_share_bps == 0,
+        };
+        if !share_valid { return err!(AppError::InvalidForkShare); }
+
+        let tale = &mut ctx.accounts.tale_account;
+        tale.fork_policy = fork_policy;
+        tale.fork_share_bps = fork_share_bps;
+        emit!(ForkPolicyUpdated { tale: tale.key(), fork_policy, fork_share_bps, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    pub fn approve_fork(ctx: Context<ApproveFork>, forker: Pubkey) -> Result<()> {
+        let approval = &mut ctx.accounts.fork_approval;
+        approval.upstream = ctx.accounts.tale_account.key();
+        approval.forker = forker;
+        approval.approved_at = Clock::get()?.unix_timestamp;
+        approval.bump = ctx.bumps.fork_approval;
+        emit!(ForkApproved { upstream: approval.upstream, forker, timestamp: approval.approved_at });
+        Ok(())
+    }
+
+    pub fn revoke_fork_approval(ctx: Context<RevokeForkApproval>) -> Result<()> {
+        let approval = &ctx.accounts.fork_approval;
+        emit!(ForkApprovalRevoked { upstream: approval.upstream, forker: approval.forker, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    // Creates a draft tale derived from a published upstream. Under `RevenueShare` the upstream's
+    // current share is copied onto the fork, so later policy changes do not affect existing forks.
+    pub fn fork_tale(
+        ctx: Context<ForkTale>,
+        tale_id: String,
+        title: String,
+        content_cid: String,
+        genre: String,
+        cover_image_cid: String,
+        thumbnail_cid: String,
+    ) -> Result<()> {
+        let upstream = &ctx.accounts.upstream_tale;
+        if upstream.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+        let upstream_share_bps = match ForkPolicy::from_u8(upstream.fork_policy)? {
+            ForkPolicy::Disallowed => return err!(AppError::ForkingDisallowed),
+            ForkPolicy::RequiresApproval if ctx.accounts.fork_approval.is_none() => return err!(AppError::ForkApprovalRequired),
+            ForkPolicy::RevenueShare => upstream.fork_share_bps,
+            _ => 0,
+        };
+
+        let bump = ctx.bumps.create.tale_account;
+        ctx.accounts.create.init_tale(bump, NewTale {
+            tale_id,
+            title,
+            content_cid,
+            genre,
+            cover_image_cid,
+            thumbnail_cid,
+            initial_status: TaleStatus::Draft as u8,
+            candy_machine_address_option: None,
+            is_governance: false,
+            is_early_access: false,
+            is_real_world: false,
+        })?;
+        let tale = &mut ctx.accounts.create.tale_account;
+        tale.upstream_tale = Some(upstream.key());
+        tale.upstream_share_bps = upstream_share_bps;
+        emit!(TaleForked {
+            tale: tale.key(),
+            upstream_tale: upstream.key(),
+            author: tale.author,
+            upstream_share_bps,
+            timestamp: tale.timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Tale Status Instructions ---
+    pub fn publish_tale(ctx: Context<ChangeTaleStatus>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_status = tale.status;
+        transition_tale(tale, TaleStatus::Published)?;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        emit_tale_status_event(tale, previous_status, ctx.accounts.author.key(), tale.timestamp);
+        Ok(())
+    }
+
+    pub fn archive_tale(ctx: Context<ChangeTaleStatus>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_status = tale.status;
+        transition_tale(tale, TaleStatus::Archived)?;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        emit_tale_status_event(tale, previous_status, ctx.accounts.author.key(), tale.timestamp);
+        Ok(())
+    }
+
+    // Archived tales come back as drafts and must be published again explicitly.
+    pub fn restore_tale(ctx: Context<ChangeTaleStatus>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_status = tale.status;
+        transition_tale(tale, TaleStatus::Draft)?;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        emit_tale_status_event(tale, previous_status, ctx.accounts.author.key(), tale.timestamp);
+        Ok(())
+    }
+
+    // --- Token Gating Instructions ---
+    // Passing `None` for a gate disables it; the matching `is_*_token_gated` flag follows the config.
+    // `collection_gates` marks, with `GATE_*` bits, the configured gates whose `mint` is a collection.
+    pub fn set_tale_gates(
+        ctx: Context<SetTaleGates>,
+        governance_gate: Option<TokenGate>,
+        early_access_gate: Option<TokenGate>,
+        real_world_asset_gate: Option<TokenGate>,
+        collection_gates: u8,
+    ) -> Result<()> {
+        let mut configured_gates = 0;
+        for (gate, gate_bit) in [
+            (&governance_gate, GATE_GOVERNANCE),
+            (&early_access_gate, GATE_EARLY_ACCESS),
+            (&real_world_asset_gate, GATE_REAL_WORLD_ASSET),
+        ] {
+            let Some(gate) = gate else { continue };
+            if gate.min_balance == 0 { return err!(AppError::InvalidGateMinBalance); }
+            if collection_gates & gate_bit != 0 && gate.min_balance != 1 {
+                return err!(AppError::InvalidCollectionGateMinBalance);
+            }
+            configured_gates |= gate_bit;
+        }
+        if collection_gates & !configured_gates != 0 { return err!(AppError::InvalidCollectionGates); }
+
+        let tale = &mut ctx.accounts.tale_account;
+        tale.is_governance_token_gated = governance_gate.is_some();
+        tale.is_early_access_token_gated = early_access_gate.is_some();
+        tale.is_real_world_asset_gated = real_world_asset_gate.is_some();
+        tale.governance_gate = governance_gate;
+        tale.early_access_gate = early_access_gate;
+        tale.real_world_asset_gate = real_world_asset_gate;
+        tale.collection_gates = collection_gates;
+        emit!(TaleGatesUpdated {
+            tale: tale.key(),
+            governance_gate,
+            early_access_gate,
+            real_world_asset_gate,
+            collection_gates,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // The reader passes one token account per enabled gate in `remaining_accounts`,
+    // in the order governance, early access, real world asset. A collection gate's token
+    // account is followed by the Token Metadata account of that token's mint.
+    pub fn grant_read_access<'info>(ctx: Context<'_, '_, 'info, 'info, GrantReadAccess<'info>>) -> Result<()> {
+        let tale = &ctx.accounts.tale_account;
+        if tale.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+
+        let reader = ctx.accounts.reader.key();
+        let enabled_gates = [
+            (tale.is_governance_token_gated, tale.governance_gate, GATE_GOVERNANCE),
+            (tale.is_early_access_token_gated, tale.early_access_gate, GATE_EARLY_ACCESS),
+            (tale.is_real_world_asset_gated, tale.real_world_asset_gate, GATE_REAL_WORLD_ASSET),
+        ];
+        let mut token_accounts = ctx.remaining_accounts.iter();
+        for (enabled, gate, gate_bit) in enabled_gates {
+            if !enabled { continue; }
+            let gate = gate.ok_or(AppError::TokenGateNotConfigured)?;
+            let token_account_info = token_accounts.next().ok_or(AppError::MissingGateTokenAccount)?;
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+            if token_account.owner != reader { return err!(AppError::GateTokenOwnerMismatch); }
+            if tale.collection_gates & gate_bit != 0 {
+                let metadata_info = token_accounts.next().ok_or(AppError::MissingGateMetadataAccount)?;
+                if verified_collection(metadata_info, &token_account.mint)? != Some(gate.mint) {
+                    return err!(AppError::GateCollectionMismatch);
+                }
+            } else if token_account.mint != gate.mint {
+                return err!(AppError::GateTokenMintMismatch);
+            }
+            if token_account.amount < gate.min_balance { return err!(AppError::InsufficientGateBalance); }
+        }
+
+        let access_pass = &mut ctx.accounts.access_pass;
+        access_pass.tale = tale.key();
+        access_pass.reader = reader;
+        access_pass.granted_at = Clock::get()?.unix_timestamp;
+        access_pass.bump = ctx.bumps.access_pass;
+        emit!(ReadAccessGranted { tale: tale.key(), reader, access_pass: access_pass.key(), timestamp: access_pass.granted_at });
+        Ok(())
+    }
+
+    // --- Collaborator Instructions ---
+    pub fn add_collaborator(ctx: Context<AddCollaborator>, wallet: Pubkey, roles: u8) -> Result<()> {
+        if roles == 0 || roles & !ALL_COLLABORATOR_ROLES != 0 { return err!(AppError::InvalidCollaboratorRoles); }
+        authorize_collaborator_change(
+            &ctx.accounts.tale_account,
+            ctx.accounts.manager.key,
+            ctx.accounts.manager_collaborator.as_deref(),
+            roles,
+        )?;
+
+        let collaborator = &mut ctx.accounts.collaborator;
+        collaborator.tale = ctx.accounts.tale_account.key();
+        collaborator.wallet = wallet;
+        collaborator.roles = roles;
+        collaborator.added_by = ctx.accounts.manager.key();
+        collaborator.timestamp = Clock::get()?.unix_timestamp;
+        collaborator.bump = ctx.bumps.collaborator;
+        collaborator.generation = ctx.accounts.tale_account.collaborator_generation;
+        emit!(CollaboratorAdded {
+            tale: collaborator.tale,
+            wallet,
+            roles,
+            added_by: collaborator.added_by,
+            timestamp: collaborator.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn update_collaborator(ctx: Context<UpdateCollaborator>, new_roles: u8) -> Result<()> {
+        if new_roles == 0 || new_roles & !ALL_COLLABORATOR_ROLES != 0 { return err!(AppError::InvalidCollaboratorRoles); }
+        let collaborator = &mut ctx.accounts.collaborator;
+        authorize_collaborator_change(
+            &ctx.accounts.tale_account,
+            ctx.accounts.manager.key,
+            ctx.accounts.manager_collaborator.as_deref(),
+            collaborator.roles | new_roles,
+        )?;
+
+        let previous_roles = collaborator.roles;
+        collaborator.roles = new_roles;
+        collaborator.generation = ctx.accounts.tale_account.collaborator_generation;
+        collaborator.timestamp = Clock::get()?.unix_timestamp;
+        emit!(CollaboratorUpdated {
+            tale: collaborator.tale,
+            wallet: collaborator.wallet,
+            previous_roles,
+            roles: new_roles,
+            updated_by: ctx.accounts.manager.key(),
+            timestamp: collaborator.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn remove_collaborator(ctx: Context<RemoveCollaborator>) -> Result<()> {
+        let collaborator = &ctx.accounts.collaborator;
+        authorize_collaborator_change(
+            &ctx.accounts.tale_account,
+            ctx.accounts.manager.key,
+            ctx.accounts.manager_collaborator.as_deref(),
+            collaborator.roles,
+        )?;
+        // A removed collaborator no longer receives a share of tips.
+        ctx.accounts.tale_account.tip_splits.retain(|split| split.wallet != collaborator.wallet);
+        emit!(CollaboratorRemoved {
+            tale: collaborator.tale,
+            wallet: collaborator.wallet,
+            removed_by: ctx.accounts.manager.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Episode Instructions ---
+    // Episodes may be managed by the tale author or by a collaborator holding the matching role,
+    // passed as the optional `collaborator` account.
+    pub fn create_episode(
+        ctx: Context<CreateEpisode>,
+        episode_id_seed: String,
+        episode_name: String,
+        content_cid: String,
+        thumbnail_cid: String,
+        image_set_id: String,
+        order: u32,
+        initial_status: u8,
+        is_nft: bool,
+        candy_machine_id: String,
+        publish_at_time_option: Option<i64>,
+        unpublish_at_time_option: Option<i64>,
+    ) -> Result<()> {
+        if episode_id_seed.len() > MAX_EPISODE_ID_SEED_LENGTH { return err!(AppError::EpisodeIdSeedTooLong); }
+        if episode_name.len() > MAX_EPISODE_NAME_LENGTH { return err!(AppError::EpisodeNameTooLong); }
+        if content_cid.len() > MAX_EPISODE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Note: Episode content CID length
+        if thumbnail_cid.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH { return err!(AppError::EpisodeThumbnailCidTooLong); }
+        if image_set_id.len() > MAX_IMAGE_SET_ID_LENGTH { return err!(AppError::ImageSetIdTooLong); }
+        if candy_machine_id.len() > MAX_CANDY_MACHINE_ID_LENGTH { return err!(AppError::CandyMachineIdTooLong); }
+        if initial_status > EpisodeStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
+        validate_episode_schedule(initial_status, publish_at_time_option, unpublish_at_time_option, true)?;
+
+        let tale_account = &mut ctx.accounts.parent_tale_account;
+        authorize_tale_role(tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        tale_account.episode_count = tale_account.episode_count.checked_add(1).ok_or(AppError::EpisodeCountOverflow)?;
+
+        let episode = &mut ctx.accounts.episode_account;
+        episode.author = *ctx.accounts.editor.key;
+        episode.parent_tale = tale_account.key();
+        episode.episode_id_seed = episode_id_seed;
+        episode.episode_name = episode_name;
+        episode.content_cid = content_cid;
+        episode.thumbnail_cid = thumbnail_cid;
+        episode.image_set_id = image_set_id;
+        episode.order = order;
+        episode.status = initial_status;
+        episode.is_nft = is_nft;
+        episode.candy_machine_id = if is_nft { candy_machine_id } else { "".to_string() };
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        episode.bump = ctx.bumps.episode_account;
+        episode.publish_at_time = publish_at_time_option;
+        episode.unpublish_at_time = unpublish_at_time_option;
+        episode.like_count = 0;
+        episode.version = EPISODE_ACCOUNT_VERSION;
+        episode.revision_count = 0;
+        episode.price = 0;
+        episode.price_mint = None;
+        episode.comment_count = 0;
+        episode.moderation_state = ModerationState::Visible as u8;
+        episode.moderation_reason = 0;
+        episode.report_count = 0;
+        episode.branches = Vec::new();
+        episode.parent_episode = None;
+        episode.inbound_branch_count = 0;
+        episode.is_ending = false;
+        episode.co_author = None;
+        episode.season = None;
+        emit!(EpisodeCreated {
+            episode: episode.key(),
+            parent_tale: episode.parent_tale,
+            author: episode.author,
+            episode_id_seed: episode.episode_id_seed.clone(),
+            episode_name: episode.episode_name.clone(),
+            content_cid: episode.content_cid.clone(),
+            order: episode.order,
+            status: episode.status,
+            timestamp: episode.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn update_episode(
+        ctx: Context<UpdateEpisode>,
+        new_episode_name: String,
+        new_content_cid: String,
+        new_thumbnail_cid: String,
+        new_image_set_id: String,
+        new_order: u32,
+        new_status: u8,
+        new_is_nft: bool,
+        new_candy_machine_id: String,
+        new_publish_at_time_option: Option<i64>,
+        new_unpublish_at_time_option: Option<i64>,
+    ) -> Result<()> {
+        if new_episode_name.len() > MAX_EPISODE_NAME_LENGTH { return err!(AppError::EpisodeNameTooLong); }
+        if new_content_cid.len() > MAX_EPISODE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); }
+        if new_thumbnail_cid.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH { return err!(AppError::EpisodeThumbnailCidTooLong); }
+        if new_image_set_id.len() > MAX_IMAGE_SET_ID_LENGTH { return err!(AppError::ImageSetIdTooLong); }
+        if new_candy_machine_id.len() > MAX_CANDY_MACHINE_ID_LENGTH { return err!(AppError::CandyMachineIdTooLong); }
+        let new_status = EpisodeStatus::from_u8(new_status)?;
+        let stored = &ctx.accounts.episode_account;
+        let publish_time_changed = stored.status != EpisodeStatus::Scheduled as u8 || stored.publish_at_time != new_publish_at_time_option;
+        validate_episode_schedule(new_status.clone() as u8, new_publish_at_time_option, new_unpublish_at_time_option, publish_time_changed)?;
+
+        authorize_tale_role(
+            &ctx.accounts.parent_tale_account,
+            ctx.accounts.editor.key,
+            ctx.accounts.collaborator.as_deref(),
+            ROLE_EDIT_EPISODES,
+        )?;
+        ctx.accounts.record_revision(ctx.bumps.revision_account, &new_content_cid, &new_thumbnail_cid)?;
+
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_status = episode.status;
+        transition_episode(episode, new_status)?;
+        episode.episode_name = new_episode_name;
+        episode.content_cid = new_content_cid;
+        episode.thumbnail_cid = new_thumbnail_cid;
+        episode.image_set_id = new_image_set_id;
+        episode.set_order(new_order)?;
+        episode.is_nft = new_is_nft;
+        episode.candy_machine_id = if new_is_nft { new_candy_machine_id } else { "".to_string() };
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        episode.publish_at_time = new_publish_at_time_option;
+        episode.unpublish_at_time = new_unpublish_at_time_option;
+        emit_episode_updated(episode, ctx.accounts.editor.key());
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), episode.timestamp);
+        Ok(())
+    }
+
+    pub fn patch_episode(ctx: Context<UpdateEpisode>, patch: EpisodePatch) -> Result<()> {
+        patch.validate()?;
+        authorize_tale_role(
+            &ctx.accounts.parent_tale_account,
+            ctx.accounts.editor.key,
+            ctx.accounts.collaborator.as_deref(),
+            ROLE_EDIT_EPISODES,
+        )?;
+        let new_content_cid = patch.content_cid.clone().unwrap_or_else(|| ctx.accounts.episode_account.content_cid.clone());
+        let new_thumbnail_cid = patch.thumbnail_cid.clone().unwrap_or_else(|| ctx.accounts.episode_account.thumbnail_cid.clone());
+        ctx.accounts.record_revision(ctx.bumps.revision_account, &new_content_cid, &new_thumbnail_cid)?;
+
+        let episode = &mut ctx.accounts.episode_account;
+        let publish_at_time = patch.publish_at_time.unwrap_or(episode.publish_at_time);
+        let unpublish_at_time = patch.unpublish_at_time.unwrap_or(episode.unpublish_at_time);
+        validate_episode_schedule(episode.status, publish_at_time, unpublish_at_time, publish_at_time != episode.publish_at_time)?;
+
+        if let Some(episode_name) = patch.episode_name { episode.episode_name = episode_name; }
+        if let Some(content_cid) = patch.content_cid { episode.content_cid = content_cid; }
+        if let Some(thumbnail_cid) = patch.thumbnail_cid { episode.thumbnail_cid = thumbnail_cid; }
+        if let Some(image_set_id) = patch.image_set_id { episode.image_set_id = image_set_id; }
+        if let Some(order) = patch.order { episode.set_order(order)?; }
+        if let Some(is_nft) = patch.is_nft { episode.is_nft = is_nft; }
+        if let Some(candy_machine_id) = patch.candy_machine_id { episode.candy_machine_id = candy_machine_id; }
+        if !episode.is_nft { episode.candy_machine_id = "".to_string(); }
+        episode.publish_at_time = publish_at_time;
+        episode.unpublish_at_time = unpublish_at_time;
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        emit_episode_updated(episode, ctx.accounts.editor.key());
+        Ok(())
+    }
+
+    // --- Season Instructions ---
+    pub fn create_season(ctx: Context<CreateSeason>, title: String, cover_image_cid: String) -> Result<()> {
+        if title.len() > MAX_TITLE_LENGTH { return err!(AppError::TitleTooLong); }
+        if cover_image_cid.len() > MAX_COVER_IMAGE_CID_LENGTH { return err!(AppError::CoverImageCidTooLong); }
+        let tale = &mut ctx.accounts.parent_tale_account;
+        authorize_tale_role(tale, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+
+        let season = &mut ctx.accounts.season;
+        season.tale = tale.key();
+        season.index = tale.next_season_index;
+        season.title = title;
+        season.cover_image_cid = cover_image_cid;
+        season.status = TaleStatus::Draft as u8;
+        season.episode_count = 0;
+        season.created_by = ctx.accounts.editor.key();
+        season.timestamp = Clock::get()?.unix_timestamp;
+        season.bump = ctx.bumps.season;
+        tale.next_season_index = tale.next_season_index.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        tale.season_count = tale.season_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        emit!(SeasonCreated {
+            season: season.key(),
+            tale: season.tale,
+            index: season.index,
+            title: season.title.clone(),
+            created_by: season.created_by,
+            timestamp: season.timestamp,
+        });
+        Ok(())
+    }
+
+    // Changing `status` additionally requires the publish role.
+    pub fn update_season(
+        ctx: Context<UpdateSeason>,
+        title: Option<String>,
+        cover_image_cid: Option<String>,
+        status: Option<u8>,
+    ) -> Result<()> {
+        if title.as_ref().is_some_and(|v| v.len() > MAX_TITLE_LENGTH) { return err!(AppError::TitleTooLong); }
+        if cover_image_cid.as_ref().is_some_and(|v| v.len() > MAX_COVER_IMAGE_CID_LENGTH) { return err!(AppError::CoverImageCidTooLong); }
+        let role = if status.is_some() { ROLE_EDIT_EPISODES | ROLE_PUBLISH } else { ROLE_EDIT_EPISODES };
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), role)?;
+
+        let season = &mut ctx.accounts.season;
+        if let Some(status) = status {
+            let next = TaleStatus::from_u8(status)?;
+            if !TaleStatus::from_u8(season.status)?.can_transition_to(&next) { return err!(AppError::InvalidTaleStatusTransition); }
+            season.status = status;
+        }
+        if let Some(title) = title { season.title = title; }
+        if let Some(cover_image_cid) = cover_image_cid { season.cover_image_cid = cover_image_cid; }
+        season.timestamp = Clock::get()?.unix_timestamp;
+        emit!(SeasonUpdated {
+            season: season.key(),
+            tale: season.tale,
+            title: season.title.clone(),
+            cover_image_cid: season.cover_image_cid.clone(),
+            status: season.status,
+            editor: ctx.accounts.editor.key(),
+            timestamp: season.timestamp,
+        });
+        Ok(())
+    }
+
+    // Episodes must be moved out first; only `close_tale_cascade` may close a season that still has some.
+    pub fn close_season(ctx: Context<CloseSeason>) -> Result<()> {
+        let tale = &mut ctx.accounts.parent_tale_account;
+        authorize_tale_role(tale, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        if ctx.accounts.season.episode_count > 0 { return err!(AppError::SeasonNotEmpty); }
+        tale.season_count = tale.season_count.checked_sub(1).ok_or(AppError::SeasonCountUnderflow)?;
+        emit!(SeasonClosed {
+            season: ctx.accounts.season.key(),
+            tale: tale.key(),
+            closed_by: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Moves an episode into `season`, or out of any season when it is omitted. The episode's
+    // current season must be passed as `previous_season` so its counter stays accurate.
+    pub fn set_episode_season(ctx: Context<SetEpisodeSeason>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        let accounts = &mut *ctx.accounts;
+        // The same account passed twice would be decremented on exit after being incremented.
+        if let (Some(season), Some(previous)) = (&accounts.season, &accounts.previous_season) {
+            if season.key() == previous.key() { return err!(AppError::EpisodeAlreadyInSeason); }
+        }
+        let episode = &mut accounts.episode_account;
+        let previous_season = episode.season;
+        match (previous_season, accounts.previous_season.as_mut()) {
+            (None, None) => {}
+            (Some(key), Some(season)) if season.key() == key => {
+                season.episode_count = season.episode_count.checked_sub(1).ok_or(AppError::EpisodeCountUnderflow)?;
+            }
+            _ => return err!(AppError::SeasonMismatch),
+        }
+        if let Some(season) = accounts.season.as_mut() {
+            season.episode_count = season.episode_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        }
+
+        episode.season = accounts.season.as_ref().map(|season| season.key());
+        emit!(EpisodeSeasonChanged {
+            episode: episode.key(),
+            previous_season,
+            new_season: episode.season,
+            editor: accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Proposal Instructions ---
+    pub fn submit_episode_proposal(
+        ctx: Context<SubmitEpisodeProposal>,
+        episode_name: String,
+        content_cid: String,
+        thumbnail_cid: String,
+    ) -> Result<()> {
+        if episode_name.len() > MAX_EPISODE_NAME_LENGTH { return err!(AppError::EpisodeNameTooLong); }
+        if content_cid.len() > MAX_EPISODE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); }
+        if thumbnail_cid.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH { return err!(AppError::EpisodeThumbnailCidTooLong); }
+        let tale = &mut ctx.accounts.tale_account;
+        if tale.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.tale = tale.key();
+        proposal.index = tale.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.episode_name = episode_name;
+        proposal.content_cid = content_cid;
+        proposal.thumbnail_cid = thumbnail_cid;
+        proposal.submitted_at = Clock::get()?.unix_timestamp;
+        proposal.bump = ctx.bumps.proposal;
+        tale.proposal_count = tale.proposal_count.checked_add(1).ok_or(AppError::ProposalCountOverflow)?;
+        tale.open_proposal_count = tale.open_proposal_count.checked_add(1).ok_or(AppError::ProposalCountOverflow)?;
+        emit!(EpisodeProposalSubmitted {
+            proposal: proposal.key(),
+            tale: proposal.tale,
+            index: proposal.index,
+            proposer: proposal.proposer,
+            episode_name: proposal.episode_name.clone(),
+            content_cid: proposal.content_cid.clone(),
+            timestamp: proposal.submitted_at,
+        });
+        Ok(())
+    }
+
+    // Creates a draft episode from the proposal. The editor pays its rent and becomes `author`;
+    // the proposer is credited as `co_author` and gets the proposal's rent back.
+    pub fn accept_proposal(ctx: Context<AcceptProposal>, episode_id_seed: String, order: u32) -> Result<()> {
+        if episode_id_seed.len() > MAX_EPISODE_ID_SEED_LENGTH { return err!(AppError::EpisodeIdSeedTooLong); }
+        let tale_account = &mut ctx.accounts.parent_tale_account;
+        authorize_tale_role(tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        tale_account.episode_count = tale_account.episode_count.checked_add(1).ok_or(AppError::EpisodeCountOverflow)?;
+        tale_account.open_proposal_count = tale_account.open_proposal_count.checked_sub(1).ok_or(AppError::ProposalCountUnderflow)?;
+
+        let proposal = &ctx.accounts.proposal;
+        let episode = &mut ctx.accounts.episode_account;
+        episode.author = *ctx.accounts.editor.key;
+        episode.parent_tale = tale_account.key();
+        episode.episode_id_seed = episode_id_seed;
+        episode.episode_name = proposal.episode_name.clone();
+        episode.content_cid = proposal.content_cid.clone();
+        episode.thumbnail_cid = proposal.thumbnail_cid.clone();
+        episode.image_set_id = "".to_string();
+        episode.order = order;
+        episode.status = EpisodeStatus::Draft as u8;
+        episode.is_nft = false;
+        episode.candy_machine_id = "".to_string();
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        episode.bump = ctx.bumps.episode_account;
+        episode.publish_at_time = None;
+        episode.unpublish_at_time = None;
+        episode.like_count = 0;
+        episode.version = EPISODE_ACCOUNT_VERSION;
+        episode.revision_count = 0;
+        episode.price = 0;
+        episode.price_mint = None;
+        episode.comment_count = 0;
+        episode.moderation_state = ModerationState::Visible as u8;
+        episode.moderation_reason = 0;
+        episode.report_count = 0;
+        episode.branches = Vec::new();
+        episode.parent_episode = None;
+        episode.inbound_branch_count = 0;
+        episode.is_ending = false;
+        episode.co_author = Some(proposal.proposer);
+        episode.season = None;
+        emit!(EpisodeCreated {
+            episode: episode.key(),
+            parent_tale: episode.parent_tale,
+            author: episode.author,
+            episode_id_seed: episode.episode_id_seed.clone(),
+            episode_name: episode.episode_name.clone(),
+            content_cid: episode.content_cid.clone(),
+            order: episode.order,
+            status: episode.status,
+            timestamp: episode.timestamp,
+        });
+        emit!(EpisodeProposalAccepted {
+            proposal: proposal.key(),
+            tale: proposal.tale,
+            episode: episode.key(),
+            proposer: proposal.proposer,
+            editor: ctx.accounts.editor.key(),
+            timestamp: episode.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn reject_proposal(ctx: Context<RejectProposal>) -> Result<()> {
+        let tale = &mut ctx.accounts.parent_tale_account;
+        authorize_tale_role(tale, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        tale.open_proposal_count = tale.open_proposal_count.checked_sub(1).ok_or(AppError::ProposalCountUnderflow)?;
+        let proposal = &ctx.accounts.proposal;
+        emit!(EpisodeProposalRejected {
+            proposal: proposal.key(),
+            tale: proposal.tale,
+            proposer: proposal.proposer,
+            editor: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Branching Instructions ---
+    // The first episode to branch into `next_episode` becomes its `parent_episode`, and stays so
+    // until every branch into `next_episode` has been detached.
+    pub fn attach_branch(ctx: Context<BranchEpisode>, label: String) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        if label.len() > MAX_BRANCH_LABEL_LENGTH { return err!(AppError::BranchLabelTooLong); }
+        let episode = &mut ctx.accounts.episode_account;
+        let next_episode = &mut ctx.accounts.next_episode;
+        if episode.is_ending { return err!(AppError::EndingCannotBranch); }
+        if next_episode.order <= episode.order { return err!(AppError::InvalidBranchTarget); }
+        if episode.branches.iter().any(|branch| branch.next_episode == next_episode.key()) { return err!(AppError::BranchExists); }
+        if episode.branches.len() >= MAX_EPISODE_BRANCHES { return err!(AppError::TooManyBranches); }
+
+        episode.branches.push(EpisodeBranch { label: label.clone(), next_episode: next_episode.key() });
+        next_episode.inbound_branch_count = next_episode.inbound_branch_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        if next_episode.parent_episode.is_none() { next_episode.parent_episode = Some(episode.key()); }
+        emit!(EpisodeBranchAttached {
+            episode: episode.key(),
+            next_episode: next_episode.key(),
+            label,
+            editor: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn detach_branch(ctx: Context<BranchEpisode>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        let episode = &mut ctx.accounts.episode_account;
+        let next_episode = &mut ctx.accounts.next_episode;
+        let position = episode.branches.iter().position(|branch| branch.next_episode == next_episode.key()).ok_or(AppError::BranchNotFound)?;
+
+        episode.branches.remove(position);
+        next_episode.inbound_branch_count = next_episode.inbound_branch_count.checked_sub(1).ok_or(AppError::BranchCountUnderflow)?;
+        if next_episode.inbound_branch_count == 0 { next_episode.parent_episode = None; }
+        emit!(EpisodeBranchDetached {
+            episode: episode.key(),
+            next_episode: next_episode.key(),
+            editor: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_episode_ending(ctx: Context<ChangeEpisodeStatus>, is_ending: bool) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_EDIT_EPISODES)?;
+        let episode = &mut ctx.accounts.episode_account;
+        if is_ending && !episode.branches.is_empty() { return err!(AppError::EndingCannotBranch); }
+        episode.is_ending = is_ending;
+        emit!(EpisodeEndingSet {
+            episode: episode.key(),
+            is_ending,
+            editor: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Episode Status Instructions ---
+    pub fn publish_episode(ctx: Context<ChangeEpisodeStatus>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_status = episode.status;
+        transition_episode(episode, EpisodeStatus::Published)?;
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), episode.timestamp);
+        Ok(())
+    }
+
+    pub fn schedule_episode(
+        ctx: Context<ChangeEpisodeStatus>,
+        publish_at_time: i64,
+        unpublish_at_time_option: Option<i64>,
+    ) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        validate_episode_schedule(EpisodeStatus::Scheduled as u8, Some(publish_at_time), unpublish_at_time_option, true)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_status = episode.status;
+        transition_episode(episode, EpisodeStatus::Scheduled)?;
+        episode.publish_at_time = Some(publish_at_time);
+        episode.unpublish_at_time = unpublish_at_time_option;
+        episode.timestamp = now;
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), now);
+        Ok(())
+    }
+
+    pub fn unschedule_episode(ctx: Context<ChangeEpisodeStatus>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        let episode = &mut ctx.accounts.episode_account;
+        if episode.status != EpisodeStatus::Scheduled as u8 { return err!(AppError::InvalidEpisodeStatusTransition); }
+        let previous_status = episode.status;
+        transition_episode(episode, EpisodeStatus::Draft)?;
+        episode.publish_at_time = None;
+        episode.unpublish_at_time = None;
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), episode.timestamp);
+        Ok(())
+    }
+
+    pub fn archive_episode(ctx: Context<ChangeEpisodeStatus>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_status = episode.status;
+        transition_episode(episode, EpisodeStatus::Archived)?;
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), episode.timestamp);
+        Ok(())
+    }
+
+    // Archived episodes come back as drafts and must be published or scheduled again explicitly.
+    pub fn restore_episode(ctx: Context<ChangeEpisodeStatus>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_status = episode.status;
+        transition_episode(episode, EpisodeStatus::Draft)?;
+        episode.publish_at_time = None;
+        episode.unpublish_at_time = None;
+        episode.timestamp = Clock::get()?.unix_timestamp;
+        emit_episode_status_event(episode, previous_status, Some(ctx.accounts.editor.key()), episode.timestamp);
+        Ok(())
+    }
+
+    pub fn delete_episode(ctx: Context<DeleteEpisode>) -> Result<()> {
+        authorize_tale_role(
+            &ctx.accounts.parent_tale_account,
+            ctx.accounts.editor.key,
+            ctx.accounts.collaborator.as_deref(),
+            ROLE_EDIT_EPISODES,
+        )?;
+        let episode = &ctx.accounts.episode_account;
+        if !episode.branches.is_empty() || episode.inbound_branch_count > 0 { return err!(AppError::EpisodeHasBranches); }
+        if episode.season.is_some() { return err!(AppError::EpisodeInSeason); }
+        let tale = &mut ctx.accounts.parent_tale_account;
+        tale.episode_count = tale.episode_count.checked_sub(1).ok_or(AppError::EpisodeCountUnderflow)?;
+        emit!(EpisodeDeleted {
+            episode: ctx.accounts.episode_account.key(),
+            parent_tale: tale.key(),
+            deleted_by: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Paid Episode Instructions ---
+    pub fn set_episode_price(ctx: Context<ChangeEpisodeStatus>, price: u64, price_mint: Option<Pubkey>) -> Result<()> {
+        authorize_tale_role(&ctx.accounts.parent_tale_account, ctx.accounts.editor.key, ctx.accounts.collaborator.as_deref(), ROLE_PUBLISH)?;
+        let episode = &mut ctx.accounts.episode_account;
+        episode.price = price;
+        episode.price_mint = if price == 0 { None } else { price_mint };
+        emit!(EpisodePriceUpdated {
+            episode: episode.key(),
+            price: episode.price,
+            price_mint: episode.price_mint,
+            updated_by: ctx.accounts.editor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Pays the episode price to the tale author, less the platform fee, and issues the reader's
+    // `EpisodePurchase` receipt. SPL-priced episodes require `mint`, `reader_token_account` and `token_program`.
+    pub fn purchase_episode(ctx: Context<PurchaseEpisode>) -> Result<()> {
+        let episode = &ctx.accounts.episode_account;
+        if episode.price == 0 { return err!(AppError::EpisodeNotForSale); }
+        if episode.status != EpisodeStatus::Published as u8 { return err!(AppError::EpisodeNotPublished); }
+        if ctx.accounts.parent_tale_account.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+
+        let accounts = &ctx.accounts;
+        let source = PaymentSource::new(
+            &accounts.reader,
+            &accounts.system_program,
+            accounts.mint.as_deref(),
+            accounts.reader_token_account.as_deref(),
+            accounts.token_program.as_ref(),
+        )?;
+        if source.mint() != episode.price_mint { return err!(AppError::PaymentMintMismatch); }
+        let upstream = upstream_share(
+            &accounts.parent_tale_account,
+            accounts.upstream_tale.as_deref(),
+            accounts.upstream_receiver.as_deref(),
+        )?;
+        let (platform_fee, upstream_amount) = source.pay_with_platform_fee(
+            &accounts.platform_config,
+            &accounts.treasury_receiver,
+            &accounts.author_receiver,
+            &accounts.parent_tale_account.author,
+            upstream.as_ref(),
+            episode.price,
+        )?;
+
+        let purchase = &mut ctx.accounts.purchase_account;
+        purchase.episode = episode.key();
+        purchase.reader = ctx.accounts.reader.key();
+        purchase.mint = episode.price_mint;
+        purchase.amount = episode.price;
+        purchase.purchased_at = Clock::get()?.unix_timestamp;
+        purchase.bump = ctx.bumps.purchase_account;
+        emit!(EpisodePurchased {
+            episode: purchase.episode,
+            parent_tale: episode.parent_tale,
+            reader: purchase.reader,
+            mint: purchase.mint,
+            amount: purchase.amount,
+            platform_fee,
+            upstream_amount,
+            timestamp: purchase.purchased_at,
+        });
+        Ok(())
+    }
+
+    // Read-only check for the backend, meant to be simulated: succeeds if the episode is free, or if
+    // the reader holds its purchase receipt or an unexpired subscription to the tale.
+    pub fn verify_episode_access(ctx: Context<VerifyEpisodeAccess>) -> Result<()> {
+        if ctx.accounts.parent_tale_account.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+        if ctx.accounts.episode_account.status != EpisodeStatus::Published as u8 { return err!(AppError::EpisodeNotPublished); }
+        if ctx.accounts.episode_account.price == 0 || ctx.accounts.purchase_account.is_some() { return Ok(()); }
+        let now = Clock::get()?.unix_timestamp;
+        match &ctx.accounts.subscription {
+            Some(subscription) if subscription.expires_at > now => Ok(()),
+            _ => err!(AppError::EpisodeAccessDenied),
+        }
+    }
+
+    // --- Subscription Instructions ---
+    pub fn set_subscription_plan(
+        ctx: Context<SetSubscriptionPlan>,
+        price: u64,
+        period_seconds: i64,
+        mint: Option<Pubkey>,
     ) -> Result<()> {
-        if new_episode_name.len() > MAX_EPISODE_NAME_LENGTH { return err!(AppError::EpisodeNameTooLong); }
-        if new_content_cid.len() > MAX_EPISODE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); }
-        if new_thumbnail_cid.len() > MAX_EPISODE_THUMBNAIL_CID_LENGTH { return err!(AppError::EpisodeThumbnailCidTooLong); }
-        if new_image_set_id.len() > MAX_IMAGE_SET_ID_LENGTH { return err!(AppError::ImageSetIdTooLong); }
-        if new_candy_machine_id.len() > MAX_CANDY_MACHINE_ID_LENGTH { return err!(AppError::CandyMachineIdTooLong); }
-        if new_status > EpisodeStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
-        if let (Some(publish_ts), Some(unpublish_ts)) = (new_publish_at_time_option, new_unpublish_at_time_option) {
-            if publish_ts >= unpublish_ts {
-                return err!(AppError::InvalidScheduleTimes);
+        if price == 0 || period_seconds <= 0 { return err!(AppError::InvalidSubscriptionPlan); }
+
+        let plan = &mut ctx.accounts.subscription_plan;
+        plan.tale = ctx.accounts.tale_account.key();
+        plan.price = price;
+        plan.period_seconds = period_seconds;
+        plan.mint = mint;
+        plan.bump = ctx.bumps.subscription_plan;
+        emit!(SubscriptionPlanUpdated {
+            tale: plan.tale,
+            price,
+            period_seconds,
+            mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn subscribe(ctx: Context<Subscribe>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.tale = ctx.accounts.payment.tale_account.key();
+        subscription.reader = ctx.accounts.payment.reader.key();
+        subscription.started_at = now;
+        subscription.expires_at = now;
+        subscription.bump = ctx.bumps.subscription;
+        ctx.accounts.payment.pay_period(subscription, &ctx.accounts.system_program, now)
+    }
+
+    // Extends from the current expiry, or from now if the subscription has already lapsed.
+    pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+        let accounts = &mut *ctx.accounts;
+        accounts.payment.pay_period(&mut accounts.subscription, &accounts.system_program, Clock::get()?.unix_timestamp)
+    }
+
+    // Closes the subscription and refunds its rent. Time already paid for is forfeited.
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        emit!(SubscriptionCancelled {
+            tale: ctx.accounts.subscription.tale,
+            reader: ctx.accounts.reader.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Platform Instructions ---
+    // Only the program's upgrade authority can create the platform config, so it cannot be front-run.
+    pub fn initialize_platform(ctx: Context<InitializePlatform>, fee_bps: u16) -> Result<()> {
+        if fee_bps > MAX_PLATFORM_FEE_BPS { return err!(AppError::PlatformFeeTooHigh); }
+
+        let config = &mut ctx.accounts.platform_config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.treasury.key();
+        config.fee_bps = fee_bps;
+        config.treasury_bump = ctx.bumps.treasury;
+        config.bump = ctx.bumps.platform_config;
+        config.moderators = Vec::new();
+        config.report_hide_threshold = 0;
+
+        // Fund the treasury up to rent exemption, so fees below that minimum can still land in it.
+        let treasury = &ctx.accounts.treasury;
+        let required_lamports = Rent::get()?.minimum_balance(0).saturating_sub(treasury.lamports());
+        if required_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer { from: ctx.accounts.admin.to_account_info(), to: treasury.to_account_info() },
+                ),
+                required_lamports,
+            )?;
+        }
+        emit!(PlatformConfigUpdated {
+            admin: config.admin,
+            treasury: config.treasury,
+            fee_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        new_admin: Option<Pubkey>,
+        new_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        if new_fee_bps.is_some_and(|fee_bps| fee_bps > MAX_PLATFORM_FEE_BPS) { return err!(AppError::PlatformFeeTooHigh); }
+
+        let config = &mut ctx.accounts.platform_config;
+        if let Some(admin) = new_admin { config.admin = admin; }
+        if let Some(fee_bps) = new_fee_bps { config.fee_bps = fee_bps; }
+        emit!(PlatformConfigUpdated {
+            admin: config.admin,
+            treasury: config.treasury,
+            fee_bps: config.fee_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn add_moderator(ctx: Context<UpdatePlatformConfig>, moderator: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.platform_config;
+        if config.moderators.contains(&moderator) { return err!(AppError::ModeratorExists); }
+        if config.moderators.len() >= MAX_MODERATORS { return err!(AppError::TooManyModerators); }
+        config.moderators.push(moderator);
+        emit!(ModeratorAdded { moderator, added_by: config.admin, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    pub fn remove_moderator(ctx: Context<UpdatePlatformConfig>, moderator: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.platform_config;
+        let position = config.moderators.iter().position(|wallet| *wallet == moderator).ok_or(AppError::ModeratorNotFound)?;
+        config.moderators.remove(position);
+        emit!(ModeratorRemoved { moderator, removed_by: config.admin, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    // Lamports are sent from the treasury PDA to `destination`, which always keeps its rent-exempt
+    // minimum; for SPL, `destination` is a token account and the treasury-owned token account is debited.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        if amount == 0 { return err!(AppError::InvalidAmount); }
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.platform_config.treasury_bump]];
+        let accounts = &ctx.accounts;
+        let mint = match (&accounts.mint, &accounts.treasury_token_account, &accounts.token_program) {
+            (None, None, None) => {
+                let withdrawable = accounts.treasury.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+                if amount > withdrawable { return err!(AppError::InsufficientTreasuryBalance); }
+                system_program::transfer(
+                    CpiContext::new_with_signer(
+                        accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: accounts.treasury.to_account_info(),
+                            to: accounts.destination.to_account_info(),
+                        },
+                        &[treasury_seeds],
+                    ),
+                    amount,
+                )?;
+                None
+            }
+            (Some(mint), Some(treasury_token_account), Some(token_program)) => {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: treasury_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: accounts.destination.to_account_info(),
+                            authority: accounts.treasury.to_account_info(),
+                        },
+                        &[treasury_seeds],
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+                Some(mint.key())
+            }
+            _ => return err!(AppError::IncompleteTokenAccounts),
+        };
+        emit!(TreasuryWithdrawn {
+            admin: accounts.admin.key(),
+            destination: accounts.destination.key(),
+            mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Moderation Instructions ---
+    // The admin and listed moderators may move content between any moderation states.
+    // `reason` is a platform-defined code recorded for appeals and transparency reports.
+    pub fn moderate_tale(ctx: Context<ModerateTale>, new_state: u8, reason: u16) -> Result<()> {
+        ModerationState::from_u8(new_state)?;
+        if !ctx.accounts.platform_config.is_moderator(ctx.accounts.moderator.key) { return err!(AppError::NotModerator); }
+
+        let tale = &mut ctx.accounts.tale_account;
+        let previous_state = tale.moderation_state;
+        tale.moderation_state = new_state;
+        tale.moderation_reason = reason;
+        emit!(TaleModerated {
+            tale: tale.key(),
+            previous_state,
+            new_state,
+            reason,
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn moderate_episode(ctx: Context<ModerateEpisode>, new_state: u8, reason: u16) -> Result<()> {
+        ModerationState::from_u8(new_state)?;
+        if !ctx.accounts.platform_config.is_moderator(ctx.accounts.moderator.key) { return err!(AppError::NotModerator); }
+
+        let episode = &mut ctx.accounts.episode_account;
+        let previous_state = episode.moderation_state;
+        episode.moderation_state = new_state;
+        episode.moderation_reason = reason;
+        emit!(EpisodeModerated {
+            episode: episode.key(),
+            parent_tale: episode.parent_tale,
+            previous_state,
+            new_state,
+            reason,
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_report_hide_threshold(ctx: Context<UpdatePlatformConfig>, report_hide_threshold: u32) -> Result<()> {
+        ctx.accounts.platform_config.report_hide_threshold = report_hide_threshold;
+        emit!(ReportHideThresholdUpdated { report_hide_threshold, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    // --- Report Instructions ---
+    // Reports the episode when `episode_account` is passed, otherwise the tale. Visible content
+    // is hidden automatically once its open reports reach the platform's threshold; such hides
+    // are attributed to the program id in the moderation event.
+    pub fn report_content(ctx: Context<ReportContent>, reason: u8, evidence_cid: String) -> Result<()> {
+        ReportReason::from_u8(reason)?;
+        if evidence_cid.len() > MAX_EVIDENCE_CID_LENGTH { return err!(AppError::EvidenceCidTooLong); }
+        let threshold = ctx.accounts.platform_config.report_hide_threshold;
+        let now = Clock::get()?.unix_timestamp;
+        let tale = &mut ctx.accounts.tale_account;
+
+        let (target, report_count) = match ctx.accounts.episode_account.as_mut() {
+            Some(episode) => {
+                episode.report_count = episode.report_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+                if threshold > 0 && episode.report_count >= threshold && episode.moderation_state == ModerationState::Visible as u8 {
+                    episode.moderation_state = ModerationState::Hidden as u8;
+                    episode.moderation_reason = AUTO_HIDE_REASON;
+                    emit!(EpisodeModerated {
+                        episode: episode.key(),
+                        parent_tale: episode.parent_tale,
+                        previous_state: ModerationState::Visible as u8,
+                        new_state: episode.moderation_state,
+                        reason: AUTO_HIDE_REASON,
+                        moderator: crate::ID,
+                        timestamp: now,
+                    });
+                }
+                (episode.key(), episode.report_count)
+            }
+            None => {
+                tale.report_count = tale.report_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+                if threshold > 0 && tale.report_count >= threshold && tale.moderation_state == ModerationState::Visible as u8 {
+                    tale.moderation_state = ModerationState::Hidden as u8;
+                    tale.moderation_reason = AUTO_HIDE_REASON;
+                    emit!(TaleModerated {
+                        tale: tale.key(),
+                        previous_state: ModerationState::Visible as u8,
+                        new_state: tale.moderation_state,
+                        reason: AUTO_HIDE_REASON,
+                        moderator: crate::ID,
+                        timestamp: now,
+                    });
+                }
+                (tale.key(), tale.report_count)
+            }
+        };
+
+        let report = &mut ctx.accounts.report;
+        report.target = target;
+        report.tale = tale.key();
+        report.reporter = ctx.accounts.reporter.key();
+        report.reason = reason;
+        report.evidence_cid = evidence_cid;
+        report.status = ReportStatus::Open as u8;
+        report.created_at = now;
+        report.resolved_by = None;
+        report.resolved_at = None;
+        report.bump = ctx.bumps.report;
+        emit!(ContentReported {
+            report: report.key(),
+            target,
+            tale: report.tale,
+            reporter: report.reporter,
+            reason,
+            evidence_cid: report.evidence_cid.clone(),
+            report_count,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    // Resolving records that the report was acted on; the takedown itself is done with
+    // `moderate_tale` / `moderate_episode`.
+    pub fn resolve_report(ctx: Context<CloseReport>) -> Result<()> {
+        ctx.accounts.close_report(ReportStatus::Resolved)
+    }
+
+    pub fn dismiss_report(ctx: Context<CloseReport>) -> Result<()> {
+        ctx.accounts.close_report(ReportStatus::Dismissed)
+    }
+
+    // For reports whose target, or its tale, has since been closed, so `CloseReport` can no longer
+    // load it. There is no report count left to adjust; the report is dismissed and its rent
+    // returned to the reporter.
+    pub fn close_orphaned_report(ctx: Context<CloseOrphanedReport>) -> Result<()> {
+        if !ctx.accounts.platform_config.is_moderator(ctx.accounts.moderator.key) { return err!(AppError::NotModerator); }
+        let report = &mut ctx.accounts.report;
+        if report.status != ReportStatus::Open as u8 { return err!(AppError::ReportNotOpen); }
+        let target_gone = ctx.accounts.target_account.owner == &system_program::ID
+            || ctx.accounts.tale_account.owner == &system_program::ID;
+        if !target_gone { return err!(AppError::ReportTargetExists); }
+
+        let now = Clock::get()?.unix_timestamp;
+        report.status = ReportStatus::Dismissed as u8;
+        report.resolved_by = Some(ctx.accounts.moderator.key());
+        report.resolved_at = Some(now);
+        emit!(ReportClosed {
+            report: report.key(),
+            target: report.target,
+            tale: report.tale,
+            reporter: report.reporter,
+            reason: report.reason,
+            status: report.status,
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    // --- Genre Registry Instructions ---
+    pub fn create_genre(ctx: Context<CreateGenre>, slug: String, display_name: String) -> Result<()> {
+        validate_genre_slug(&slug)?;
+        if display_name.len() > MAX_GENRE_DISPLAY_NAME_LENGTH { return err!(AppError::DisplayNameTooLong); }
+
+        let genre = &mut ctx.accounts.genre_account;
+        genre.slug = slug;
+        genre.display_name = display_name;
+        genre.tale_count = 0;
+        genre.is_active = true;
+        genre.bump = ctx.bumps.genre_account;
+        emit!(GenreCreated {
+            genre: genre.key(),
+            slug: genre.slug.clone(),
+            display_name: genre.display_name.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Inactive genres keep their tales but cannot be chosen for new or updated tales.
+    pub fn update_genre(ctx: Context<UpdateGenre>, display_name: Option<String>, is_active: Option<bool>) -> Result<()> {
+        if display_name.as_ref().is_some_and(|v| v.len() > MAX_GENRE_DISPLAY_NAME_LENGTH) { return err!(AppError::DisplayNameTooLong); }
+
+        let genre = &mut ctx.accounts.genre_account;
+        if let Some(display_name) = display_name { genre.display_name = display_name; }
+        if let Some(is_active) = is_active { genre.is_active = is_active; }
+        emit!(GenreUpdated {
+            genre: genre.key(),
+            slug: genre.slug.clone(),
+            display_name: genre.display_name.clone(),
+            is_active: genre.is_active,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Tipping Instructions ---
+    // Every wallet in `splits` must already be a collaborator on the tale; pass their
+    // TaleCollaborator accounts in `remaining_accounts` in the same order.
+    pub fn set_tip_splits<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetTipSplits<'info>>,
+        splits: Vec<TipSplit>,
+    ) -> Result<()> {
+        if splits.len() > MAX_TIP_SPLITS { return err!(AppError::TooManyTipSplits); }
+        if ctx.remaining_accounts.len() != splits.len() { return err!(AppError::SplitWalletNotCollaborator); }
+        let tale_key = ctx.accounts.tale_account.key();
+        let generation = ctx.accounts.tale_account.collaborator_generation;
+        let mut total_bps: u64 = 0;
+        for (index, (split, collaborator_info)) in splits.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            if split.share_bps == 0 || splits[..index].iter().any(|other| other.wallet == split.wallet) {
+                return err!(AppError::InvalidTipSplit);
+            }
+            total_bps += split.share_bps as u64;
+            let collaborator = Account::<TaleCollaborator>::try_from(collaborator_info)?;
+            if collaborator.tale != tale_key || collaborator.wallet != split.wallet || collaborator.generation != generation {
+                return err!(AppError::SplitWalletNotCollaborator);
+            }
+        }
+        if total_bps > BPS_DENOMINATOR { return err!(AppError::InvalidTipSplit); }
+
+        let tale = &mut ctx.accounts.tale_account;
+        tale.tip_splits = splits;
+        emit!(TipSplitsUpdated { tale: tale_key, tip_splits: tale.tip_splits.clone(), timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+
+    // Tips are paid in lamports unless `mint`, `tipper_token_account` and `token_program` are passed.
+    // Split recipients (wallets, or their token accounts for SPL) follow in `remaining_accounts`
+    // in the order of `Tale.tip_splits`.
+    pub fn tip_tale<'info>(ctx: Context<'_, '_, 'info, 'info, TipTale<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.process_tip(ctx.remaining_accounts, amount, None)
+    }
+
+    pub fn tip_episode<'info>(ctx: Context<'_, '_, 'info, 'info, TipEpisode<'info>>, amount: u64) -> Result<()> {
+        let episode = ctx.accounts.episode_account.key();
+        ctx.accounts.tip.process_tip(ctx.remaining_accounts, amount, Some(episode))
+    }
+
+    // --- Migration Instructions ---
+    // Permissionless: grows an account created before layout versioning to the current size and
+    // stamps the current version. Whoever calls it pays the extra rent.
+    pub fn migrate_tale(ctx: Context<MigrateTale>) -> Result<()> {
+        let info = ctx.accounts.tale_account.to_account_info();
+        if info.data_len() != LEGACY_TALE_ACCOUNT_SPACE { return err!(AppError::AccountAlreadyMigrated); }
+        grow_account(&info, &ctx.accounts.payer.to_account_info(), &ctx.accounts.system_program.to_account_info(), TALE_ACCOUNT_SPACE)?;
+        let mut tale = decode_legacy_account::<LegacyTale, Tale>(&info, TALE_ACCOUNT_SPACE)?;
+
+        let from_version = tale.version;
+        tale.version = TALE_ACCOUNT_VERSION;
+        rewrite_account(&info, &tale)?;
+        emit!(TaleMigrated {
+            tale: info.key(),
+            from_version,
+            to_version: TALE_ACCOUNT_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn migrate_episode(ctx: Context<MigrateEpisode>) -> Result<()> {
+        let info = ctx.accounts.episode_account.to_account_info();
+        if info.data_len() != LEGACY_EPISODE_ACCOUNT_SPACE { return err!(AppError::AccountAlreadyMigrated); }
+        grow_account(&info, &ctx.accounts.payer.to_account_info(), &ctx.accounts.system_program.to_account_info(), EPISODE_ACCOUNT_SPACE)?;
+        let mut episode = decode_legacy_account::<LegacyEpisode, Episode>(&info, EPISODE_ACCOUNT_SPACE)?;
+        if episode.parent_tale != ctx.accounts.parent_tale_account.key() { return err!(AppError::EpisodeTaleMismatch); }
+        // Legacy episodes were never counted; this is the one point each of them is seen.
+        let tale = &mut ctx.accounts.parent_tale_account;
+        tale.episode_count = tale.episode_count.checked_add(1).ok_or(AppError::EpisodeCountOverflow)?;
+
+        let from_version = episode.version;
+        episode.version = EPISODE_ACCOUNT_VERSION;
+        rewrite_account(&info, &episode)?;
+        emit!(EpisodeMigrated {
+            episode: info.key(),
+            from_version,
+            to_version: EPISODE_ACCOUNT_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Schedule Crank Instructions ---
+    // Permissionless: anyone may crank an episode whose publish/unpublish time has passed.
+    pub fn process_episode_schedule(ctx: Context<ProcessEpisodeSchedule>) -> Result<()> {
+        let episode = &mut ctx.accounts.episode_account;
+        let now = Clock::get()?.unix_timestamp;
+        let previous_status = episode.status;
+        if !apply_episode_schedule(episode, now) {
+            return err!(AppError::EpisodeScheduleNotDue);
+        }
+        emit_episode_status_event(episode, previous_status, None, now);
+        Ok(())
+    }
+
+    // Batched variant: writable Episode accounts are passed in `remaining_accounts`.
+    // Episodes with nothing due are skipped so one stale entry does not fail the whole batch.
+    pub fn process_episode_schedules<'info>(ctx: Context<'_, '_, 'info, 'info, ProcessEpisodeSchedules>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        for episode_info in ctx.remaining_accounts.iter() {
+            if !episode_info.is_writable { return err!(ErrorCode::ConstraintMut); }
+            let mut episode = Account::<Episode>::try_from(episode_info)?;
+            let previous_status = episode.status;
+            if apply_episode_schedule(&mut episode, now) {
+                episode.exit(&crate::ID)?;
+                emit_episode_status_event(&episode, previous_status, None, now);
             }
         }
+        Ok(())
+    }
+
+    // --- Like Instructions ---
+    // Each like creates a LikeRecord PDA seeded by (target, user), so a wallet can only like once.
+    // Unliking closes the record and refunds its rent to the user.
+    pub fn like_episode(ctx: Context<LikeEpisode>) -> Result<()> {
+        let episode = &mut ctx.accounts.episode_account;
+        episode.like_count = episode.like_count.checked_add(1).ok_or(AppError::LikeCountOverflow)?;
+
+        let like_record = &mut ctx.accounts.like_record;
+        like_record.target = episode.key();
+        like_record.user = ctx.accounts.user.key();
+        like_record.timestamp = Clock::get()?.unix_timestamp;
+        like_record.bump = ctx.bumps.like_record;
+        emit!(EpisodeLiked {
+            episode: episode.key(),
+            user: like_record.user,
+            like_count: episode.like_count,
+            timestamp: like_record.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn unlike_episode(ctx: Context<UnlikeEpisode>) -> Result<()> {
+        let episode = &mut ctx.accounts.episode_account;
+        episode.like_count = episode.like_count.checked_sub(1).ok_or(AppError::LikeCountUnderflow)?;
+        emit!(EpisodeUnliked {
+            episode: episode.key(),
+            user: ctx.accounts.user.key(),
+            like_count: episode.like_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn like_tale(ctx: Context<LikeTale>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        tale.like_count = tale.like_count.checked_add(1).ok_or(AppError::LikeCountOverflow)?;
+
+        let like_record = &mut ctx.accounts.like_record;
+        like_record.target = tale.key();
+        like_record.user = ctx.accounts.user.key();
+        like_record.timestamp = Clock::get()?.unix_timestamp;
+        like_record.bump = ctx.bumps.like_record;
+        emit!(TaleLiked {
+            tale: tale.key(),
+            user: like_record.user,
+            like_count: tale.like_count,
+            timestamp: like_record.timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn unlike_tale(ctx: Context<UnlikeTale>) -> Result<()> {
+        let tale = &mut ctx.accounts.tale_account;
+        tale.like_count = tale.like_count.checked_sub(1).ok_or(AppError::LikeCountUnderflow)?;
+        emit!(TaleUnliked {
+            tale: tale.key(),
+            user: ctx.accounts.user.key(),
+            like_count: tale.like_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Comment Instructions ---
+    pub fn post_comment(ctx: Context<PostComment>, body: String, parent: Option<u32>) -> Result<()> {
+        validate_comment_body(&body)?;
+        let episode = &mut ctx.accounts.episode_account;
+        if episode.status != EpisodeStatus::Published as u8 { return err!(AppError::EpisodeNotPublished); }
+        if parent.is_some_and(|parent| parent >= episode.comment_count) { return err!(AppError::InvalidParentComment); }
+
+        let comment = &mut ctx.accounts.comment;
+        comment.episode = episode.key();
+        comment.index = episode.comment_count;
+        comment.author = ctx.accounts.author.key();
+        comment.parent = parent;
+        comment.body = body;
+        comment.is_hidden = false;
+        comment.created_at = Clock::get()?.unix_timestamp;
+        comment.edited_at = None;
+        comment.bump = ctx.bumps.comment;
+        episode.comment_count = episode.comment_count.checked_add(1).ok_or(AppError::CommentCountOverflow)?;
+        emit!(CommentPosted {
+            comment: comment.key(),
+            episode: comment.episode,
+            index: comment.index,
+            author: comment.author,
+            parent,
+            body: comment.body.clone(),
+            timestamp: comment.created_at,
+        });
+        Ok(())
+    }
+
+    pub fn edit_comment(ctx: Context<EditComment>, new_body: String) -> Result<()> {
+        validate_comment_body(&new_body)?;
+        let comment = &mut ctx.accounts.comment;
+        let now = Clock::get()?.unix_timestamp;
+        comment.body = new_body;
+        comment.edited_at = Some(now);
+        emit!(CommentEdited {
+            comment: comment.key(),
+            episode: comment.episode,
+            index: comment.index,
+            body: comment.body.clone(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    // `Episode.comment_count` is not decremented so later indices stay valid.
+    pub fn delete_comment(ctx: Context<DeleteComment>) -> Result<()> {
+        let comment = &ctx.accounts.comment;
+        emit!(CommentDeleted {
+            comment: comment.key(),
+            episode: comment.episode,
+            index: comment.index,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Hidden comments stay on-chain; clients are expected to collapse them.
+    pub fn set_comment_hidden(ctx: Context<ModerateComment>, hidden: bool) -> Result<()> {
+        let comment = &mut ctx.accounts.comment;
+        comment.is_hidden = hidden;
+        emit!(CommentVisibilityChanged {
+            comment: comment.key(),
+            episode: comment.episode,
+            index: comment.index,
+            is_hidden: hidden,
+            moderator: ctx.accounts.tale_author.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // --- Reader Progress Instructions ---
+    // Creates the reader's progress account on first use.
+    pub fn update_progress(ctx: Context<UpdateProgress>) -> Result<()> {
+        let episode = &ctx.accounts.episode_account;
+        if episode.status != EpisodeStatus::Published as u8 { return err!(AppError::EpisodeNotPublished); }
+        let tale = &ctx.accounts.tale_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        let progress = &mut ctx.accounts.progress;
+        progress.tale = tale.key();
+        progress.reader = ctx.accounts.reader.key();
+        progress.bump = ctx.bumps.progress;
+        progress.last_episode = episode.key();
+        progress.last_order = episode.order;
+        progress.updated_at = now;
+        let moved_forward = match progress.highest_order {
+            Some(highest) => episode.order > highest,
+            None => true,
+        };
+        if moved_forward {
+            progress.highest_order = Some(episode.order);
+            progress.episodes_read = progress.episodes_read.saturating_add(1);
+        }
+        emit!(ReaderProgressUpdated {
+            tale: progress.tale,
+            reader: progress.reader,
+            episode: progress.last_episode,
+            order: progress.last_order,
+            episodes_read: progress.episodes_read,
+            timestamp: now,
+        });
+
+        if progress.completed_at.is_none() && tale.episode_count > 0 && progress.episodes_read >= tale.episode_count {
+            progress.completed_at = Some(now);
+            emit!(TaleCompleted { tale: progress.tale, reader: progress.reader, timestamp: now });
+        }
+        Ok(())
+    }
+
+    pub fn add_bookmark(ctx: Context<AddBookmark>) -> Result<()> {
+        let episode = ctx.accounts.episode_account.key();
+        let progress = &mut ctx.accounts.progress;
+        if progress.bookmarks.contains(&episode) { return err!(AppError::BookmarkExists); }
+        if progress.bookmarks.len() >= MAX_BOOKMARKS { return err!(AppError::TooManyBookmarks); }
+        progress.bookmarks.push(episode);
+        progress.updated_at = Clock::get()?.unix_timestamp;
+        emit!(BookmarkAdded { tale: progress.tale, reader: progress.reader, episode, timestamp: progress.updated_at });
+        Ok(())
+    }
+
+    // Takes the episode key rather than the account so bookmarks of deleted episodes can be removed.
+    pub fn remove_bookmark(ctx: Context<RemoveBookmark>, episode: Pubkey) -> Result<()> {
+        let progress = &mut ctx.accounts.progress;
+        let position = progress.bookmarks.iter().position(|bookmark| *bookmark == episode).ok_or(AppError::BookmarkNotFound)?;
+        progress.bookmarks.remove(position);
+        progress.updated_at = Clock::get()?.unix_timestamp;
+        emit!(BookmarkRemoved { tale: progress.tale, reader: progress.reader, episode, timestamp: progress.updated_at });
+        Ok(())
+    }
+
+    // --- Profile Instructions ---
+    pub fn create_profile(ctx: Context<CreateProfile>, display_name: String, bio_cid: String, avatar_cid: String) -> Result<()> {
+        validate_profile_fields(&display_name, &bio_cid, &avatar_cid)?;
+        let profile = &mut ctx.accounts.profile;
+        profile.wallet = ctx.accounts.wallet.key();
+        profile.handle = None;
+        profile.display_name = display_name;
+        profile.bio_cid = bio_cid;
+        profile.avatar_cid = avatar_cid;
+        profile.created_at = Clock::get()?.unix_timestamp;
+        profile.updated_at = profile.created_at;
+        profile.bump = ctx.bumps.profile;
+        emit!(ProfileCreated {
+            profile: profile.key(),
+            wallet: profile.wallet,
+            display_name: profile.display_name.clone(),
+            timestamp: profile.created_at,
+        });
+        Ok(())
+    }
+
+    pub fn update_profile(
+        ctx: Context<UpdateProfile>,
+        display_name: Option<String>,
+        bio_cid: Option<String>,
+        avatar_cid: Option<String>,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        if let Some(display_name) = display_name { profile.display_name = display_name; }
+        if let Some(bio_cid) = bio_cid { profile.bio_cid = bio_cid; }
+        if let Some(avatar_cid) = avatar_cid { profile.avatar_cid = avatar_cid; }
+        validate_profile_fields(&profile.display_name, &profile.bio_cid, &profile.avatar_cid)?;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        emit!(ProfileUpdated {
+            profile: profile.key(),
+            wallet: profile.wallet,
+            display_name: profile.display_name.clone(),
+            bio_cid: profile.bio_cid.clone(),
+            avatar_cid: profile.avatar_cid.clone(),
+            timestamp: profile.updated_at,
+        });
+        Ok(())
+    }
+
+    pub fn claim_handle(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+        validate_handle(&handle)?;
+        let profile = &mut ctx.accounts.profile;
+        if profile.handle.is_some() { return err!(AppError::ProfileHasHandle); }
+
+        let handle_account = &mut ctx.accounts.handle_account;
+        handle_account.handle = handle.clone();
+        handle_account.owner = profile.wallet;
+        handle_account.bump = ctx.bumps.handle_account;
+        profile.handle = Some(handle.clone());
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        emit!(HandleClaimed { handle, owner: profile.wallet, timestamp: profile.updated_at });
+        Ok(())
+    }
+
+    // Frees the handle for anyone to claim and refunds its rent.
+    pub fn release_handle(ctx: Context<ReleaseHandle>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        let handle = profile.handle.take().ok_or(AppError::ProfileHasNoHandle)?;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        emit!(HandleReleased { handle, owner: profile.wallet, timestamp: profile.updated_at });
+        Ok(())
+    }
+
+    // Both wallets sign; the recipient's profile must not already hold a handle.
+    pub fn transfer_handle(ctx: Context<TransferHandle>) -> Result<()> {
+        let to_profile = &mut ctx.accounts.to_profile;
+        if to_profile.handle.is_some() { return err!(AppError::ProfileHasHandle); }
+        let from_profile = &mut ctx.accounts.from_profile;
+        let handle = from_profile.handle.take().ok_or(AppError::ProfileHasNoHandle)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        from_profile.updated_at = now;
+        to_profile.handle = Some(handle.clone());
+        to_profile.updated_at = now;
+        ctx.accounts.handle_account.owner = to_profile.wallet;
+        emit!(HandleTransferred { handle, from: from_profile.wallet, to: to_profile.wallet, timestamp: now });
+        Ok(())
+    }
+}
+
+// --- Contexts ---
+#[derive(Accounts)]
+#[instruction(tale_id: String, title: String, content_cid: String, genre: String)]
+pub struct CreateTale<'info> {
+    #[account(
+        init,
+        payer = author,
+        space = TALE_ACCOUNT_SPACE, // Updated space
+        seeds = [b"tale", author.key().as_ref(), tale_id.as_bytes()],
+        bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"genre", genre.as_bytes()],
+        bump = genre_account.bump
+    )]
+    pub genre_account: Account<'info, Genre>,
+    // Links the tale to the author's profile when passed.
+    #[account(seeds = [b"profile", author.key().as_ref()], bump = author_profile.bump)]
+    pub author_profile: Option<Account<'info, AuthorProfile>>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Arguments of `create_tale`, shared with `fork_tale`.
+struct NewTale {
+    tale_id: String,
+    title: String,
+    content_cid: String,
+    genre: String,
+    cover_image_cid: String,
+    thumbnail_cid: String,
+    initial_status: u8,
+    candy_machine_address_option: Option<Pubkey>,
+    is_governance: bool,
+    is_early_access: bool,
+    is_real_world: bool,
+}
+
+impl<'info> CreateTale<'info> {
+    fn init_tale(&mut self, bump: u8, new_tale: NewTale) -> Result<()> {
+        if new_tale.tale_id.len() > MAX_TALE_ID_LENGTH { return err!(AppError::EpisodeIdSeedTooLong); }
+        if new_tale.title.len() > MAX_TITLE_LENGTH { return err!(AppError::TitleTooLong); }
+        if new_tale.content_cid.len() > MAX_TALE_CONTENT_CID_LENGTH { return err!(AppError::ContentCidTooLong); } // Error originates here
+        if new_tale.genre.len() > MAX_GENRE_LENGTH { return err!(AppError::GenreTooLong); }
+        if new_tale.cover_image_cid.len() > MAX_COVER_IMAGE_CID_LENGTH { return err!(AppError::CoverImageCidTooLong); }
+        if new_tale.thumbnail_cid.len() > MAX_THUMBNAIL_CID_LENGTH { return err!(AppError::ThumbnailCidTooLong); }
+        if new_tale.initial_status > TaleStatus::Archived as u8 { return err!(AppError::InvalidStatus); }
+        let genre_account = &mut self.genre_account;
+        if !genre_account.is_active { return err!(AppError::GenreInactive); }
+        genre_account.tale_count = genre_account.tale_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+
+        let tale = &mut self.tale_account;
+        tale.author = *self.author.key;
+        tale.tale_id = new_tale.tale_id;
+        tale.title = new_tale.title;
+        tale.content_cid = new_tale.content_cid;
+        tale.genre = new_tale.genre;
+        tale.cover_image_cid = new_tale.cover_image_cid;
+        tale.thumbnail_cid = new_tale.thumbnail_cid;
+        tale.status = new_tale.initial_status;
+        tale.timestamp = Clock::get()?.unix_timestamp;
+        tale.bump = bump;
+        tale.candy_machine_address = new_tale.candy_machine_address_option;
+        tale.is_governance_token_gated = new_tale.is_governance;
+        tale.is_early_access_token_gated = new_tale.is_early_access;
+        tale.is_real_world_asset_gated = new_tale.is_real_world;
+        tale.like_count = 0;
+        tale.governance_gate = None;
+        tale.early_access_gate = None;
+        tale.real_world_asset_gate = None;
+        tale.episode_count = 0;
+        tale.version = TALE_ACCOUNT_VERSION;
+        tale.total_tips = 0;
+        tale.total_tip_lamports = 0;
+        tale.tip_splits = Vec::new();
+        tale.author_profile = self.author_profile.as_ref().map(|profile| profile.key());
+        tale.moderation_state = ModerationState::Visible as u8;
+        tale.moderation_reason = 0;
+        tale.report_count = 0;
+        tale.pending_author = None;
+        tale.collaborator_generation = 0;
+        tale.fork_policy = ForkPolicy::Disallowed as u8;
+        tale.fork_share_bps = 0;
+        tale.upstream_tale = None;
+        tale.upstream_share_bps = 0;
+        tale.proposal_count = 0;
+        tale.open_proposal_count = 0;
+        tale.next_season_index = 0;
+        tale.season_count = 0;
+        tale.creator = Some(self.author.key());
+        emit!(TaleCreated {
+            tale: tale.key(),
+            author: tale.author,
+            tale_id: tale.tale_id.clone(),
+            title: tale.title.clone(),
+            content_cid: tale.content_cid.clone(),
+            genre: tale.genre.clone(),
+            status: tale.status,
+            timestamp: tale.timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateTale<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub tale_account: Account<'info, Tale>,
+    // The genre being switched to; required only when the genre changes.
+    #[account(
+        mut,
+        seeds = [b"genre", genre_account.slug.as_bytes()],
+        bump = genre_account.bump
+    )]
+    pub genre_account: Option<Account<'info, Genre>>,
+    /// CHECK: The genre PDA of the current `tale_account.genre`; validated in `release_genre`.
+    #[account(mut)]
+    pub previous_genre: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+}
+
+impl<'info> UpdateTale<'info> {
+    fn change_genre(&mut self, new_genre: String) -> Result<()> {
+        if self.tale_account.genre == new_genre { return Ok(()); }
+        let genre_account = self.genre_account.as_mut().ok_or(AppError::MissingGenreAccount)?;
+        if genre_account.slug != new_genre { return err!(AppError::GenreAccountMismatch); }
+        if !genre_account.is_active { return err!(AppError::GenreInactive); }
+
+        release_genre(&self.tale_account.genre, self.previous_genre.as_deref())?;
+        genre_account.tale_count = genre_account.tale_count.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        self.tale_account.genre = new_genre;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct DeleteTale<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized,
+        close = author
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"genre", tale_account.genre.as_bytes()],
+        bump = genre_account.bump
+    )]
+    pub genre_account: Account<'info, Genre>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTaleCascade<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    // Only updated by the call that closes the tale.
+    #[account(
+        mut,
+        seeds = [b"genre", tale_account.genre.as_bytes()],
+        bump = genre_account.bump
+    )]
+    pub genre_account: Account<'info, Genre>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferTale<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTaleTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        constraint = tale_account.pending_author == Some(new_author.key()) @ AppError::NotPendingAuthor
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub new_author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetForkPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(forker: Pubkey)]
+pub struct ApproveFork<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init,
+        payer = author,
+        space = FORK_APPROVAL_ACCOUNT_SPACE,
+        seeds = [b"fork_approval", tale_account.key().as_ref(), forker.as_ref()],
+        bump
+    )]
+    pub fork_approval: Account<'info, ForkApproval>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeForkApproval<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"fork_approval", tale_account.key().as_ref(), fork_approval.forker.as_ref()],
+        bump = fork_approval.bump,
+        close = author
+    )]
+    pub fork_approval: Account<'info, ForkApproval>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForkTale<'info> {
+    pub create: CreateTale<'info>,
+    #[account(
+        seeds = [b"tale", upstream_tale.seed_namespace(), upstream_tale.tale_id.as_bytes()],
+        bump = upstream_tale.bump,
+        constraint = upstream_tale.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub upstream_tale: Box<Account<'info, Tale>>,
+    #[account(
+        seeds = [b"fork_approval", upstream_tale.key().as_ref(), create.author.key().as_ref()],
+        bump = fork_approval.bump
+    )]
+    pub fork_approval: Option<Account<'info, ForkApproval>>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeTaleStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddCollaborator<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init,
+        payer = manager,
+        space = TALE_COLLABORATOR_ACCOUNT_SPACE,
+        seeds = [b"collaborator", tale_account.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub collaborator: Account<'info, TaleCollaborator>,
+    #[account(
+        seeds = [b"collaborator", tale_account.key().as_ref(), manager.key().as_ref()],
+        bump = manager_collaborator.bump
+    )]
+    pub manager_collaborator: Option<Account<'info, TaleCollaborator>>,
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollaborator<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"collaborator", tale_account.key().as_ref(), collaborator.wallet.as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Account<'info, TaleCollaborator>,
+    #[account(
+        seeds = [b"collaborator", tale_account.key().as_ref(), manager.key().as_ref()],
+        bump = manager_collaborator.bump
+    )]
+    pub manager_collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCollaborator<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"collaborator", tale_account.key().as_ref(), collaborator.wallet.as_ref()],
+        bump = collaborator.bump,
+        close = added_by
+    )]
+    pub collaborator: Account<'info, TaleCollaborator>,
+    #[account(
+        seeds = [b"collaborator", tale_account.key().as_ref(), manager.key().as_ref()],
+        bump = manager_collaborator.bump
+    )]
+    pub manager_collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub manager: Signer<'info>,
+    // Rent goes back to whoever added (and paid for) the collaborator record.
+    #[account(mut, address = collaborator.added_by @ AppError::Unauthorized)]
+    pub added_by: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTaleGates<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GrantReadAccess<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved,
+        constraint = tale_account.moderation_state != ModerationState::Hidden as u8 @ AppError::ContentHidden
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init_if_needed,
+        payer = reader,
+        space = ACCESS_PASS_ACCOUNT_SPACE,
+        seeds = [b"access", tale_account.key().as_ref(), reader.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+    #[account(mut)]
+    pub reader: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(episode_id_seed: String)]
+pub struct CreateEpisode<'info> {
+    #[account(
+        init,
+        payer = editor,
+        space = EPISODE_ACCOUNT_SPACE,
+        seeds = [
+            b"episode".as_ref(),
+            parent_tale_account.key().as_ref(),
+            episode_id_seed.as_bytes()
+        ],
+        bump
+    )]
+    pub episode_account: Account<'info, Episode>,
+    #[account(
+        mut,
+        constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    #[account(mut)]
+    pub editor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEpisode<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub episode_account: Account<'info, Episode>,
+    #[account(constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    // Required only when the content or thumbnail CID changes.
+    #[account(
+        init,
+        payer = editor,
+        space = EPISODE_REVISION_ACCOUNT_SPACE,
+        seeds = [b"revision", episode_account.key().as_ref(), &episode_account.revision_count.to_le_bytes()],
+        bump
+    )]
+    pub revision_account: Option<Account<'info, EpisodeRevision>>,
+    #[account(mut)]
+    pub editor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateEpisode<'info> {
+    // Snapshots the current content into `revision_account` before it is overwritten.
+    fn record_revision(&mut self, revision_bump: Option<u8>, new_content_cid: &str, new_thumbnail_cid: &str) -> Result<()> {
+        let episode = &mut self.episode_account;
+        let content_changed = episode.content_cid != new_content_cid || episode.thumbnail_cid != new_thumbnail_cid;
+        let (revision, bump) = match (self.revision_account.as_mut(), revision_bump, content_changed) {
+            (Some(revision), Some(bump), true) => (revision, bump),
+            (None, _, true) => return err!(AppError::RevisionAccountRequired),
+            (Some(_), _, false) => return err!(AppError::RevisionAccountNotNeeded),
+            _ => return Ok(()),
+        };
+
+        revision.episode = episode.key();
+        revision.revision = episode.revision_count;
+        revision.content_cid = episode.content_cid.clone();
+        revision.thumbnail_cid = episode.thumbnail_cid.clone();
+        revision.editor = self.editor.key();
+        revision.timestamp = Clock::get()?.unix_timestamp;
+        revision.bump = bump;
+        episode.revision_count = episode.revision_count.checked_add(1).ok_or(AppError::RevisionCountOverflow)?;
+        emit!(EpisodeRevisionRecorded {
+            episode: revision.episode,
+            revision: revision.revision,
+            previous_content_cid: revision.content_cid.clone(),
+            previous_thumbnail_cid: revision.thumbnail_cid.clone(),
+            editor: revision.editor,
+            timestamp: revision.timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateSeason<'info> {
+    #[account(
+        mut,
+        constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        init,
+        payer = editor,
+        space = SEASON_ACCOUNT_SPACE,
+        seeds = [b"season", parent_tale_account.key().as_ref(), &parent_tale_account.next_season_index.to_le_bytes()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    #[account(mut)]
+    pub editor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSeason<'info> {
+    #[account(constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"season", season.tale.as_ref(), &season.index.to_le_bytes()],
+        bump = season.bump,
+        constraint = season.tale == parent_tale_account.key() @ AppError::SeasonMismatch
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    #[account(mut)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"season", season.tale.as_ref(), &season.index.to_le_bytes()],
+        bump = season.bump,
+        constraint = season.tale == parent_tale_account.key() @ AppError::SeasonMismatch,
+        close = created_by
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+    #[account(mut, address = season.created_by @ AppError::Unauthorized)]
+    pub created_by: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEpisodeSeason<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub episode_account: Box<Account<'info, Episode>>,
+    #[account(constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved)]
+    pub parent_tale_account: Box<Account<'info, Tale>>,
+    // The season to move into; omit to remove the episode from its season.
+    #[account(mut, constraint = season.tale == parent_tale_account.key() @ AppError::SeasonMismatch)]
+    pub season: Option<Box<Account<'info, Season>>>,
+    #[account(mut, constraint = previous_season.tale == parent_tale_account.key() @ AppError::SeasonMismatch)]
+    pub previous_season: Option<Box<Account<'info, Season>>>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitEpisodeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init,
+        payer = proposer,
+        space = EPISODE_PROPOSAL_ACCOUNT_SPACE,
+        seeds = [b"proposal", tale_account.key().as_ref(), &tale_account.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, EpisodeProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(episode_id_seed: String)]
+pub struct AcceptProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.tale.as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        close = proposer
+    )]
+    pub proposal: Box<Account<'info, EpisodeProposal>>,
+    #[account(
+        init,
+        payer = editor,
+        space = EPISODE_ACCOUNT_SPACE,
+        seeds = [
+            b"episode".as_ref(),
+            parent_tale_account.key().as_ref(),
+            episode_id_seed.as_bytes()
+        ],
+        bump
+    )]
+    pub episode_account: Box<Account<'info, Episode>>,
+    #[account(
+        mut,
+        constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub parent_tale_account: Box<Account<'info, Tale>>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    #[account(mut, address = proposal.proposer @ AppError::Unauthorized)]
+    pub proposer: SystemAccount<'info>,
+    #[account(mut)]
+    pub editor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.tale.as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        close = proposer
+    )]
+    pub proposal: Account<'info, EpisodeProposal>,
+    #[account(mut)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    #[account(mut, address = proposal.proposer @ AppError::Unauthorized)]
+    pub proposer: SystemAccount<'info>,
+    pub editor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BranchEpisode<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub episode_account: Box<Account<'info, Episode>>,
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            next_episode.parent_tale.as_ref(),
+            next_episode.episode_id_seed.as_bytes()
+        ],
+        bump = next_episode.bump,
+        constraint = next_episode.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch
+    )]
+    pub next_episode: Box<Account<'info, Episode>>,
+    #[account(constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved)]
+    pub parent_tale_account: Box<Account<'info, Tale>>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeEpisodeStatus<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub episode_account: Account<'info, Episode>,
+    #[account(constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteEpisode<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        close = author
+    )]
+    pub episode_account: Account<'info, Episode>,
+    #[account(mut)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"collaborator", parent_tale_account.key().as_ref(), editor.key().as_ref()],
+        bump = collaborator.bump
+    )]
+    pub collaborator: Option<Account<'info, TaleCollaborator>>,
+    pub editor: Signer<'info>,
+    // Rent goes back to whoever created the episode, which may be a collaborator.
+    #[account(mut, address = episode_account.author @ AppError::Unauthorized)]
+    pub author: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseEpisode<'info> {
+    #[account(
+        init,
+        payer = reader,
+        space = EPISODE_PURCHASE_ACCOUNT_SPACE,
+        seeds = [b"purchase", episode_account.key().as_ref(), reader.key().as_ref()],
+        bump
+    )]
+    pub purchase_account: Box<Account<'info, EpisodePurchase>>,
+    #[account(
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved,
+        constraint = episode_account.moderation_state != ModerationState::Hidden as u8 @ AppError::ContentHidden
+    )]
+    pub episode_account: Box<Account<'info, Episode>>,
+    #[account(
+        constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved,
+        constraint = parent_tale_account.moderation_state != ModerationState::Hidden as u8 @ AppError::ContentHidden
+    )]
+    pub parent_tale_account: Box<Account<'info, Tale>>,
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    /// CHECK: The tale author's wallet, or their token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub author_receiver: UncheckedAccount<'info>,
+    /// CHECK: The treasury PDA, or its token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub treasury_receiver: UncheckedAccount<'info>,
+    /// CHECK: The fork's upstream tale, needed when `upstream_share_bps` is set; validated when paid.
+    pub upstream_tale: Option<UncheckedAccount<'info>>,
+    /// CHECK: The upstream author's wallet, or their token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub upstream_receiver: Option<UncheckedAccount<'info>>,
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub reader_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub reader: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyEpisodeAccess<'info> {
+    #[account(
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump,
+        constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved,
+        constraint = episode_account.moderation_state != ModerationState::Hidden as u8 @ AppError::ContentHidden
+    )]
+    pub episode_account: Account<'info, Episode>,
+    #[account(
+        seeds = [b"tale", parent_tale_account.seed_namespace(), parent_tale_account.tale_id.as_bytes()],
+        bump = parent_tale_account.bump,
+        constraint = parent_tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved,
+        constraint = parent_tale_account.moderation_state != ModerationState::Hidden as u8 @ AppError::ContentHidden
+    )]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(
+        seeds = [b"purchase", episode_account.key().as_ref(), reader.key().as_ref()],
+        bump = purchase_account.bump
+    )]
+    pub purchase_account: Option<Account<'info, EpisodePurchase>>,
+    #[account(
+        seeds = [b"subscription", episode_account.parent_tale.as_ref(), reader.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// CHECK: Only the key is used to derive the receipt and subscription addresses.
+    pub reader: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSubscriptionPlan<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init_if_needed,
+        payer = author,
+        space = SUBSCRIPTION_PLAN_ACCOUNT_SPACE,
+        seeds = [b"plan", tale_account.key().as_ref()],
+        bump
+    )]
+    pub subscription_plan: Account<'info, SubscriptionPlan>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Accounts needed to charge one subscription period; shared by `subscribe` and `renew_subscription`.
+#[derive(Accounts)]
+pub struct SubscriptionPayment<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub tale_account: Box<Account<'info, Tale>>,
+    #[account(seeds = [b"plan", tale_account.key().as_ref()], bump = subscription_plan.bump)]
+    pub subscription_plan: Box<Account<'info, SubscriptionPlan>>,
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    /// CHECK: The tale author's wallet, or their token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub author_receiver: UncheckedAccount<'info>,
+    /// CHECK: The treasury PDA, or its token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub treasury_receiver: UncheckedAccount<'info>,
+    /// CHECK: The fork's upstream tale, needed when `upstream_share_bps` is set; validated when paid.
+    pub upstream_tale: Option<UncheckedAccount<'info>>,
+    /// CHECK: The upstream author's wallet, or their token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub upstream_receiver: Option<UncheckedAccount<'info>>,
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub reader_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub reader: Signer<'info>,
+}
+
+impl<'info> SubscriptionPayment<'info> {
+    // Charges one period at the plan's current price and pushes `expires_at` forward by it.
+    // Removed tales are rejected by the context; only published tales take new payments.
+    fn pay_period(&self, subscription: &mut Subscription, system_program: &Program<'info, System>, now: i64) -> Result<()> {
+        if self.tale_account.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+        let plan = &self.subscription_plan;
+        let source = PaymentSource::new(
+            &self.reader,
+            system_program,
+            self.mint.as_deref(),
+            self.reader_token_account.as_deref(),
+            self.token_program.as_ref(),
+        )?;
+        if source.mint() != plan.mint { return err!(AppError::PaymentMintMismatch); }
+        let upstream = upstream_share(&self.tale_account, self.upstream_tale.as_deref(), self.upstream_receiver.as_deref())?;
+        let (platform_fee, upstream_amount) = source.pay_with_platform_fee(
+            &self.platform_config,
+            &self.treasury_receiver,
+            &self.author_receiver,
+            &self.tale_account.author,
+            upstream.as_ref(),
+            plan.price,
+        )?;
+
+        subscription.expires_at = subscription.expires_at.max(now)
+            .checked_add(plan.period_seconds)
+            .ok_or(AppError::ArithmeticOverflow)?;
+        emit!(SubscriptionPaid {
+            tale: subscription.tale,
+            reader: subscription.reader,
+            mint: plan.mint,
+            amount: plan.price,
+            platform_fee,
+            upstream_amount,
+            expires_at: subscription.expires_at,
+            timestamp: now,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    pub payment: SubscriptionPayment<'info>,
+    #[account(
+        init,
+        payer = payment.reader,
+        space = SUBSCRIPTION_ACCOUNT_SPACE,
+        seeds = [b"subscription", payment.tale_account.key().as_ref(), payment.reader.key().as_ref()],
+        bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    pub payment: SubscriptionPayment<'info>,
+    #[account(
+        mut,
+        seeds = [b"subscription", payment.tale_account.key().as_ref(), payment.reader.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.tale.as_ref(), reader.key().as_ref()],
+        bump = subscription.bump,
+        close = reader
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(mut)]
+    pub reader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = PLATFORM_CONFIG_ACCOUNT_SPACE,
+        seeds = [b"platform_config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ AppError::Unauthorized)]
+    pub program: Program<'info, crate::program::TaleStory>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ AppError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ AppError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ AppError::Unauthorized,
+        has_one = treasury @ AppError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: Any wallet for lamport withdrawals; for SPL the token program validates it as a token account.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModerateTale<'info> {
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ModerateEpisode<'info> {
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [
+            b"episode".as_ref(),
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
+        ],
+        bump = episode_account.bump
+    )]
+    pub episode_account: Account<'info, Episode>,
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReportContent<'info> {
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Box<Account<'info, Tale>>,
+    #[account(mut, constraint = episode_account.parent_tale == tale_account.key() @ AppError::EpisodeTaleMismatch)]
+    pub episode_account: Option<Box<Account<'info, Episode>>>,
+    #[account(
+        init,
+        payer = reporter,
+        space = CONTENT_REPORT_ACCOUNT_SPACE,
+        seeds = [
+            b"report".as_ref(),
+            episode_account.as_ref().map_or(tale_account.key(), |episode| episode.key()).as_ref(),
+            reporter.key().as_ref()
+        ],
+        bump
+    )]
+    pub report: Box<Account<'info, ContentReport>>,
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-        let episode = &mut ctx.accounts.episode_account;
-        episode.episode_name = new_episode_name;
-        episode.content_cid = new_content_cid;
-        episode.thumbnail_cid = new_thumbnail_cid;
-        episode.image_set_id = new_image_set_id;
-        episode.order = new_order;
-        episode.status = new_status;
-        episode.is_nft = new_is_nft;
-        episode.candy_machine_id = if new_is_nft { new_candy_machine_id } else { "".to_string() };
-        episode.timestamp = Clock::get()?.unix_timestamp;
-        episode.publish_at_time = new_publish_at_time_option;
-        episode.unpublish_at_time = new_unpublish_at_time_option;
-        msg!("Episode updated: {}", episode.episode_name);
-        Ok(())
-    }
+// Shared by `resolve_report` and `dismiss_report`. Pass `episode_account` for episode reports.
+#[derive(Accounts)]
+pub struct CloseReport<'info> {
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    #[account(
+        mut,
+        seeds = [b"report", report.target.as_ref(), report.reporter.as_ref()],
+        bump = report.bump,
+        constraint = report.tale == tale_account.key() @ AppError::ReportTargetMismatch,
+        close = reporter
+    )]
+    pub report: Box<Account<'info, ContentReport>>,
+    #[account(mut)]
+    pub tale_account: Box<Account<'info, Tale>>,
+    #[account(mut, constraint = episode_account.key() == report.target @ AppError::ReportTargetMismatch)]
+    pub episode_account: Option<Box<Account<'info, Episode>>>,
+    #[account(mut, address = report.reporter @ AppError::Unauthorized)]
+    pub reporter: SystemAccount<'info>,
+    pub moderator: Signer<'info>,
+}
 
-    pub fn delete_episode(ctx: Context<DeleteEpisode>) -> Result<()> {
-        msg!("Episode deleted: {}", ctx.accounts.episode_account.episode_name);
-        Ok(())
-    }
+impl<'info> CloseReport<'info> {
+    fn close_report(&mut self, status: ReportStatus) -> Result<()> {
+        if !self.platform_config.is_moderator(self.moderator.key) { return err!(AppError::NotModerator); }
+        let report = &mut self.report;
+        if report.status != ReportStatus::Open as u8 { return err!(AppError::ReportNotOpen); }
+        match self.episode_account.as_mut() {
+            Some(episode) => episode.report_count = episode.report_count.saturating_sub(1),
+            None => {
+                if report.target != self.tale_account.key() { return err!(AppError::ReportTargetMismatch); }
+                self.tale_account.report_count = self.tale_account.report_count.saturating_sub(1);
+            }
+        }
 
-    pub fn like_episode(ctx: Context<LikeEpisode>) -> Result<()> {
-        let episode = &mut ctx.accounts.episode_account;
-        episode.like_count = episode.like_count.checked_add(1).ok_or(AppError::LikeCountOverflow)?;
-        msg!("Episode '{}' liked by {}. New like count: {}", episode.episode_name, ctx.accounts.user.key(), episode.like_count);
+        let now = Clock::get()?.unix_timestamp;
+        report.status = status as u8;
+        report.resolved_by = Some(self.moderator.key());
+        report.resolved_at = Some(now);
+        emit!(ReportClosed {
+            report: report.key(),
+            target: report.target,
+            tale: report.tale,
+            reporter: report.reporter,
+            reason: report.reason,
+            status: report.status,
+            moderator: self.moderator.key(),
+            timestamp: now,
+        });
         Ok(())
     }
+}
 
-    pub fn like_tale(ctx: Context<LikeTale>) -> Result<()> {
-        let tale = &mut ctx.accounts.tale_account;
-        tale.like_count = tale.like_count.checked_add(1).ok_or(AppError::LikeCountOverflow)?;
-        msg!("Tale '{}' liked by {}. New like count: {}", tale.title, ctx.accounts.user.key(), tale.like_count);
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct CloseOrphanedReport<'info> {
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    #[account(
+        mut,
+        seeds = [b"report", report.target.as_ref(), report.reporter.as_ref()],
+        bump = report.bump,
+        close = reporter
+    )]
+    pub report: Box<Account<'info, ContentReport>>,
+    /// CHECK: Only its owner is read, to tell whether the reported tale or episode still exists.
+    #[account(address = report.target @ AppError::ReportTargetMismatch)]
+    pub target_account: UncheckedAccount<'info>,
+    /// CHECK: Only its owner is read, to tell whether the reported content's tale still exists.
+    #[account(address = report.tale @ AppError::ReportTargetMismatch)]
+    pub tale_account: UncheckedAccount<'info>,
+    #[account(mut, address = report.reporter @ AppError::Unauthorized)]
+    pub reporter: SystemAccount<'info>,
+    pub moderator: Signer<'info>,
 }
 
-// --- Contexts ---
 #[derive(Accounts)]
-#[instruction(tale_id: String)]
-pub struct CreateTale<'info> {
+#[instruction(slug: String)]
+pub struct CreateGenre<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ AppError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
     #[account(
         init,
-        payer = author,
-        space = TALE_ACCOUNT_SPACE, // Updated space
-        seeds = [b"tale", tale_id.as_bytes()],
+        payer = admin,
+        space = GENRE_ACCOUNT_SPACE,
+        seeds = [b"genre", slug.as_bytes()],
         bump
     )]
-    pub tale_account: Account<'info, Tale>,
+    pub genre_account: Account<'info, Genre>,
     #[account(mut)]
-    pub author: Signer<'info>,
+    pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateTale<'info> {
+pub struct UpdateGenre<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ AppError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
     #[account(
         mut,
-        seeds = [b"tale", tale_account.tale_id.as_bytes()],
+        seeds = [b"genre", genre_account.slug.as_bytes()],
+        bump = genre_account.bump
+    )]
+    pub genre_account: Account<'info, Genre>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTipSplits<'info> {
+    #[account(
+        mut,
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
         bump = tale_account.bump,
-        has_one = author @ AppError::Unauthorized
+        has_one = author @ AppError::Unauthorized,
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
     )]
     pub tale_account: Account<'info, Tale>,
-    #[account(mut)]
     pub author: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct DeleteTale<'info> {
+pub struct TipTale<'info> {
     #[account(
         mut,
-        seeds = [b"tale", tale_account.tale_id.as_bytes()],
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
         bump = tale_account.bump,
-        has_one = author @ AppError::Unauthorized,
-        close = author
+        constraint = tale_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
     )]
-    pub tale_account: Account<'info, Tale>,
+    pub tale_account: Box<Account<'info, Tale>>,
+    #[account(seeds = [b"platform_config"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    /// CHECK: The author's wallet, or their token account of `mint`; validated when paid.
     #[account(mut)]
-    pub author: Signer<'info>,
+    pub author_receiver: UncheckedAccount<'info>,
+    /// CHECK: The treasury PDA, or its token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub treasury_receiver: UncheckedAccount<'info>,
+    /// CHECK: The fork's upstream tale, needed when `upstream_share_bps` is set; validated when paid.
+    pub upstream_tale: Option<UncheckedAccount<'info>>,
+    /// CHECK: The upstream author's wallet, or their token account of `mint`; validated when paid.
+    #[account(mut)]
+    pub upstream_receiver: Option<UncheckedAccount<'info>>,
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub tipper_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TipTale<'info> {
+    // Platform fee first, then the upstream share for forks, then each collaborator split of the
+    // amount left after the fee, then the rest to the author.
+    fn process_tip(&mut self, split_receivers: &[AccountInfo<'info>], amount: u64, episode: Option<Pubkey>) -> Result<()> {
+        if amount == 0 { return err!(AppError::InvalidAmount); }
+        let tale = &mut self.tale_account;
+        if tale.status != TaleStatus::Published as u8 { return err!(AppError::TaleNotPublished); }
+        if split_receivers.len() < tale.tip_splits.len() { return err!(AppError::MissingPaymentRecipient); }
+
+        let source = PaymentSource::new(
+            &self.tipper,
+            &self.system_program,
+            self.mint.as_deref(),
+            self.tipper_token_account.as_deref(),
+            self.token_program.as_ref(),
+        )?;
+        let platform_fee = bps_share(amount, self.platform_config.fee_bps)?;
+        source.pay(&self.treasury_receiver, &self.platform_config.treasury, platform_fee)?;
+
+        let after_fee = amount - platform_fee;
+        let upstream = upstream_share(tale, self.upstream_tale.as_deref(), self.upstream_receiver.as_deref())?;
+        let upstream_amount = source.pay_upstream(upstream.as_ref(), after_fee)?;
+        let mut author_amount = after_fee - upstream_amount;
+        for (split, receiver) in tale.tip_splits.iter().zip(split_receivers.iter()) {
+            let share = bps_share(after_fee, split.share_bps)?.min(author_amount);
+            source.pay(receiver, &split.wallet, share)?;
+            author_amount -= share;
+        }
+        source.pay(&self.author_receiver, &tale.author, author_amount)?;
+
+        tale.total_tips = tale.total_tips.checked_add(1).ok_or(AppError::ArithmeticOverflow)?;
+        if source.mint().is_none() {
+            tale.total_tip_lamports = tale.total_tip_lamports.checked_add(amount).ok_or(AppError::ArithmeticOverflow)?;
+        }
+        emit!(TipSent {
+            tale: tale.key(),
+            episode,
+            tipper: self.tipper.key(),
+            mint: source.mint(),
+            amount,
+            platform_fee,
+            upstream_amount,
+            author_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
-#[instruction(episode_id_seed: String)]
-pub struct CreateEpisode<'info> {
+pub struct TipEpisode<'info> {
+    pub tip: TipTale<'info>,
     #[account(
-        init,
-        payer = author,
-        space = EPISODE_ACCOUNT_SPACE,
+        constraint = episode_account.parent_tale == tip.tale_account.key() @ AppError::EpisodeTaleMismatch,
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
+    )]
+    pub episode_account: Box<Account<'info, Episode>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTale<'info> {
+    /// CHECK: May still be too small to deserialize as a `Tale`; the discriminator is checked
+    /// when it is deserialized after the realloc.
+    #[account(mut, owner = crate::ID)]
+    pub tale_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateEpisode<'info> {
+    /// CHECK: May still be too small to deserialize as an `Episode`; the discriminator is checked
+    /// when it is deserialized after the realloc.
+    #[account(mut, owner = crate::ID)]
+    pub episode_account: UncheckedAccount<'info>,
+    // Must already be migrated itself.
+    #[account(mut)]
+    pub parent_tale_account: Account<'info, Tale>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessEpisodeSchedule<'info> {
+    #[account(
+        mut,
         seeds = [
             b"episode".as_ref(),
-            parent_tale_account.key().as_ref(),
-            episode_id_seed.as_bytes()
+            episode_account.parent_tale.as_ref(),
+            episode_account.episode_id_seed.as_bytes()
         ],
+        bump = episode_account.bump
+    )]
+    pub episode_account: Account<'info, Episode>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessEpisodeSchedules {}
+
+#[derive(Accounts)]
+pub struct LikeEpisode<'info> {
+    #[account(mut)]
+    pub episode_account: Account<'info, Episode>,
+    #[account(
+        init,
+        payer = user,
+        space = LIKE_RECORD_ACCOUNT_SPACE,
+        seeds = [b"like", episode_account.key().as_ref(), user.key().as_ref()],
         bump
     )]
+    pub like_record: Account<'info, LikeRecord>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlikeEpisode<'info> {
+    #[account(mut)]
     pub episode_account: Account<'info, Episode>,
     #[account(
-        constraint = parent_tale_account.author == author.key() @ AppError::Unauthorized
+        mut,
+        seeds = [b"like", episode_account.key().as_ref(), user.key().as_ref()],
+        bump = like_record.bump,
+        has_one = user @ AppError::Unauthorized,
+        close = user
     )]
-    pub parent_tale_account: Account<'info, Tale>,
+    pub like_record: Account<'info, LikeRecord>,
     #[account(mut)]
-    pub author: Signer<'info>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LikeTale<'info> {
+    #[account(mut)]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        init,
+        payer = user,
+        space = LIKE_RECORD_ACCOUNT_SPACE,
+        seeds = [b"like", tale_account.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub like_record: Account<'info, LikeRecord>,
+    #[account(mut)]
+    pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateEpisode<'info> {
+pub struct UnlikeTale<'info> {
+    #[account(mut)]
+    pub tale_account: Account<'info, Tale>,
+    #[account(
+        mut,
+        seeds = [b"like", tale_account.key().as_ref(), user.key().as_ref()],
+        bump = like_record.bump,
+        has_one = user @ AppError::Unauthorized,
+        close = user
+    )]
+    pub like_record: Account<'info, LikeRecord>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostComment<'info> {
     #[account(
         mut,
         seeds = [
@@ -411,43 +5409,199 @@ pub struct UpdateEpisode<'info> {
             episode_account.episode_id_seed.as_bytes()
         ],
         bump = episode_account.bump,
-        has_one = author @ AppError::Unauthorized
+        constraint = episode_account.moderation_state != ModerationState::Removed as u8 @ AppError::ContentRemoved
     )]
     pub episode_account: Account<'info, Episode>,
+    #[account(
+        init,
+        payer = author,
+        space = COMMENT_ACCOUNT_SPACE,
+        seeds = [b"comment", episode_account.key().as_ref(), &episode_account.comment_count.to_le_bytes()],
+        bump
+    )]
+    pub comment: Account<'info, Comment>,
     #[account(mut)]
     pub author: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DeleteEpisode<'info> {
+pub struct EditComment<'info> {
     #[account(
         mut,
-        seeds = [
-            b"episode".as_ref(),
-            episode_account.parent_tale.as_ref(),
-            episode_account.episode_id_seed.as_bytes()
-        ],
-        bump = episode_account.bump,
+        seeds = [b"comment", comment.episode.as_ref(), &comment.index.to_le_bytes()],
+        bump = comment.bump,
+        has_one = author @ AppError::Unauthorized
+    )]
+    pub comment: Account<'info, Comment>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteComment<'info> {
+    #[account(
+        mut,
+        seeds = [b"comment", comment.episode.as_ref(), &comment.index.to_le_bytes()],
+        bump = comment.bump,
         has_one = author @ AppError::Unauthorized,
         close = author
     )]
-    pub episode_account: Account<'info, Episode>,
+    pub comment: Account<'info, Comment>,
     #[account(mut)]
     pub author: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct LikeEpisode<'info> {
+pub struct ModerateComment<'info> {
+    #[account(
+        mut,
+        seeds = [b"comment", comment.episode.as_ref(), &comment.index.to_le_bytes()],
+        bump = comment.bump,
+        constraint = comment.episode == episode_account.key() @ AppError::CommentEpisodeMismatch
+    )]
+    pub comment: Account<'info, Comment>,
+    #[account(constraint = episode_account.parent_tale == parent_tale_account.key() @ AppError::EpisodeTaleMismatch)]
+    pub episode_account: Account<'info, Episode>,
+    #[account(constraint = parent_tale_account.author == tale_author.key() @ AppError::Unauthorized)]
+    pub parent_tale_account: Account<'info, Tale>,
+    pub tale_author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgress<'info> {
+    #[account(
+        seeds = [b"tale", tale_account.seed_namespace(), tale_account.tale_id.as_bytes()],
+        bump = tale_account.bump
+    )]
+    pub tale_account: Account<'info, Tale>,
+    #[account(constraint = episode_account.parent_tale == tale_account.key() @ AppError::EpisodeTaleMismatch)]
+    pub episode_account: Account<'info, Episode>,
+    #[account(
+        init_if_needed,
+        payer = reader,
+        space = READER_PROGRESS_ACCOUNT_SPACE,
+        seeds = [b"progress", tale_account.key().as_ref(), reader.key().as_ref()],
+        bump
+    )]
+    pub progress: Account<'info, ReaderProgress>,
     #[account(mut)]
+    pub reader: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddBookmark<'info> {
+    #[account(
+        mut,
+        seeds = [b"progress", progress.tale.as_ref(), reader.key().as_ref()],
+        bump = progress.bump
+    )]
+    pub progress: Account<'info, ReaderProgress>,
+    #[account(constraint = episode_account.parent_tale == progress.tale @ AppError::EpisodeTaleMismatch)]
     pub episode_account: Account<'info, Episode>,
+    pub reader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveBookmark<'info> {
+    #[account(
+        mut,
+        seeds = [b"progress", progress.tale.as_ref(), reader.key().as_ref()],
+        bump = progress.bump
+    )]
+    pub progress: Account<'info, ReaderProgress>,
+    pub reader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = wallet,
+        space = AUTHOR_PROFILE_ACCOUNT_SPACE,
+        seeds = [b"profile", wallet.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, AuthorProfile>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub wallet: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct LikeTale<'info> {
+pub struct UpdateProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", wallet.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, AuthorProfile>,
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct ClaimHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", wallet.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, AuthorProfile>,
+    #[account(
+        init,
+        payer = wallet,
+        space = HANDLE_ACCOUNT_SPACE,
+        seeds = [b"handle", handle.as_bytes()],
+        bump
+    )]
+    pub handle_account: Account<'info, Handle>,
     #[account(mut)]
-    pub tale_account: Account<'info, Tale>,
+    pub wallet: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", wallet.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, AuthorProfile>,
+    #[account(
+        mut,
+        seeds = [b"handle", handle_account.handle.as_bytes()],
+        bump = handle_account.bump,
+        constraint = handle_account.owner == wallet.key() @ AppError::Unauthorized,
+        close = wallet
+    )]
+    pub handle_account: Account<'info, Handle>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", from_wallet.key().as_ref()],
+        bump = from_profile.bump
+    )]
+    pub from_profile: Account<'info, AuthorProfile>,
+    #[account(
+        mut,
+        seeds = [b"profile", to_wallet.key().as_ref()],
+        bump = to_profile.bump
+    )]
+    pub to_profile: Account<'info, AuthorProfile>,
+    #[account(
+        mut,
+        seeds = [b"handle", handle_account.handle.as_bytes()],
+        bump = handle_account.bump,
+        constraint = handle_account.owner == from_wallet.key() @ AppError::Unauthorized
+    )]
+    pub handle_account: Account<'info, Handle>,
+    pub from_wallet: Signer<'info>,
+    pub to_wallet: Signer<'info>,
 }
\ No newline at end of file
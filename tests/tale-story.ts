@@ -0,0 +1,2184 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { TaleStory } from "../target/types/tale_story";
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+const TALE_DRAFT = 0;
+const TALE_PUBLISHED = 1;
+const TALE_ARCHIVED = 2;
+const EPISODE_DRAFT = 0;
+const EPISODE_PUBLISHED = 1;
+const EPISODE_SCHEDULED = 2;
+const EPISODE_ARCHIVED = 3;
+const MODERATION_VISIBLE = 0;
+const MODERATION_HIDDEN = 1;
+const MODERATION_REMOVED = 2;
+const ROLE_EDIT_EPISODES = 1 << 0;
+const ROLE_PUBLISH = 1 << 1;
+const ROLE_MANAGE_COLLABORATORS = 1 << 2;
+const FORK_POLICY_REQUIRES_APPROVAL = 2;
+const FORK_POLICY_REVENUE_SHARE = 3;
+const GATE_GOVERNANCE = 1 << 0;
+const GATE_EARLY_ACCESS = 1 << 1;
+
+const FEE_BPS = 500;
+const GENRE = "fantasy";
+
+describe("tale-story", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.taleStory as Program<TaleStory>;
+  const connection = provider.connection;
+  const admin = provider.wallet.publicKey;
+  const systemProgram = SystemProgram.programId;
+
+  const u32 = (value: number) => {
+    const bytes = Buffer.alloc(4);
+    bytes.writeUInt32LE(value);
+    return bytes;
+  };
+  const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const talePda = (creator: PublicKey, taleId: string) =>
+    pda(Buffer.from("tale"), creator.toBuffer(), Buffer.from(taleId));
+  const episodePda = (tale: PublicKey, episodeId: string) =>
+    pda(Buffer.from("episode"), tale.toBuffer(), Buffer.from(episodeId));
+  const collaboratorPda = (tale: PublicKey, wallet: PublicKey) =>
+    pda(Buffer.from("collaborator"), tale.toBuffer(), wallet.toBuffer());
+  const genrePda = (slug: string) => pda(Buffer.from("genre"), Buffer.from(slug));
+
+  const purchasePda = (episode: PublicKey, reader: PublicKey) =>
+    pda(Buffer.from("purchase"), episode.toBuffer(), reader.toBuffer());
+  const subscriptionPda = (tale: PublicKey, reader: PublicKey) =>
+    pda(Buffer.from("subscription"), tale.toBuffer(), reader.toBuffer());
+  const accessPda = (tale: PublicKey, reader: PublicKey) =>
+    pda(Buffer.from("access"), tale.toBuffer(), reader.toBuffer());
+
+  const platformConfig = pda(Buffer.from("platform_config"));
+  const treasury = pda(Buffer.from("treasury"));
+  const genreAccount = genrePda(GENRE);
+
+  const balance = (wallet: PublicKey) => connection.getBalance(wallet);
+  const fee = (amount: number) => Math.floor((amount * FEE_BPS) / 10_000);
+
+  const airdrop = async (wallet: PublicKey) => {
+    const signature = await connection.requestAirdrop(wallet, 10 * LAMPORTS_PER_SOL);
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+    await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight });
+  };
+
+  const newWallet = async () => {
+    const wallet = Keypair.generate();
+    await airdrop(wallet.publicKey);
+    return wallet;
+  };
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+  const chainTime = async () => (await connection.getBlockTime(await connection.getSlot())) as number;
+  const waitUntil = async (timestamp: number) => {
+    while ((await chainTime()) < timestamp) await sleep(500);
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (err) {
+      expect(err).to.be.instanceOf(anchor.AnchorError);
+      expect((err as anchor.AnchorError).error.errorCode.code).to.equal(code);
+      return;
+    }
+    expect.fail(`expected the transaction to fail with ${code}`);
+  };
+
+  // For failures raised outside the program, such as initializing an account that already exists.
+  const expectFailure = async (promise: Promise<unknown>) => {
+    let failed = false;
+    try {
+      await promise;
+    } catch (err) {
+      failed = true;
+    }
+    expect(failed, "expected the transaction to fail").to.equal(true);
+  };
+
+  const createTale = async (creator: Keypair, taleId: string, status = TALE_PUBLISHED, genre = GENRE) => {
+    const tale = talePda(creator.publicKey, taleId);
+    await program.methods
+      .createTale(taleId, "The Long Night", "bafytalecid", genre, "", "", status, null, false, false, false)
+      .accountsPartial({
+        taleAccount: tale,
+        genreAccount: genrePda(genre),
+        authorProfile: null,
+        author: creator.publicKey,
+        systemProgram,
+      })
+      .signers([creator])
+      .rpc();
+    return tale;
+  };
+
+  type EpisodeOptions = {
+    status?: number;
+    order?: number;
+    withCollaborator?: boolean;
+    publishAt?: number;
+    unpublishAt?: number;
+  };
+
+  const createEpisode = async (tale: PublicKey, editor: Keypair, episodeId: string, options: EpisodeOptions = {}) => {
+    const episode = episodePda(tale, episodeId);
+    await program.methods
+      .createEpisode(
+        episodeId,
+        `Episode ${episodeId}`,
+        "bafyepisodecid",
+        "",
+        "",
+        options.order ?? 1,
+        options.status ?? EPISODE_PUBLISHED,
+        false,
+        "",
+        options.publishAt === undefined ? null : new BN(options.publishAt),
+        options.unpublishAt === undefined ? null : new BN(options.unpublishAt)
+      )
+      .accountsPartial({
+        episodeAccount: episode,
+        parentTaleAccount: tale,
+        collaborator: options.withCollaborator ? collaboratorPda(tale, editor.publicKey) : null,
+        editor: editor.publicKey,
+        systemProgram,
+      })
+      .signers([editor])
+      .rpc();
+    return episode;
+  };
+
+  const emptyEpisodePatch = {
+    episodeName: null,
+    contentCid: null,
+    thumbnailCid: null,
+    imageSetId: null,
+    order: null,
+    isNft: null,
+    candyMachineId: null,
+    publishAtTime: null,
+    unpublishAtTime: null,
+  };
+  const patchEpisode = (tale: PublicKey, episode: PublicKey, editor: Keypair, patch: Partial<typeof emptyEpisodePatch>) =>
+    program.methods
+      .patchEpisode({ ...emptyEpisodePatch, ...patch })
+      .accountsPartial({
+        episodeAccount: episode,
+        parentTaleAccount: tale,
+        collaborator: null,
+        revisionAccount: null,
+        editor: editor.publicKey,
+        systemProgram,
+      })
+      .signers([editor])
+      .rpc();
+
+  const deleteEpisode = (tale: PublicKey, episode: PublicKey, editor: Keypair) =>
+    program.methods
+      .deleteEpisode()
+      .accountsPartial({
+        episodeAccount: episode,
+        parentTaleAccount: tale,
+        collaborator: null,
+        editor: editor.publicKey,
+        author: editor.publicKey,
+      })
+      .signers([editor])
+      .rpc();
+  const deleteTale = (tale: PublicKey, author: Keypair, genre = GENRE) =>
+    program.methods
+      .deleteTale()
+      .accountsPartial({ taleAccount: tale, genreAccount: genrePda(genre), author: author.publicKey })
+      .signers([author])
+      .rpc();
+
+  const addCollaborator = (tale: PublicKey, manager: Keypair, wallet: PublicKey, roles: number, viaCollaborator = false) =>
+    program.methods
+      .addCollaborator(wallet, roles)
+      .accountsPartial({
+        taleAccount: tale,
+        collaborator: collaboratorPda(tale, wallet),
+        managerCollaborator: viaCollaborator ? collaboratorPda(tale, manager.publicKey) : null,
+        manager: manager.publicKey,
+        systemProgram,
+      })
+      .signers([manager])
+      .rpc();
+
+  const verifyEpisodeAccess = (
+    tale: PublicKey,
+    episode: PublicKey,
+    reader: PublicKey,
+    { purchased = false, subscribed = false } = {}
+  ) =>
+    program.methods
+      .verifyEpisodeAccess()
+      .accountsPartial({
+        episodeAccount: episode,
+        parentTaleAccount: tale,
+        purchaseAccount: purchased ? purchasePda(episode, reader) : null,
+        subscription: subscribed ? subscriptionPda(tale, reader) : null,
+        reader,
+      })
+      .rpc();
+
+  const moderateTale = (tale: PublicKey, state: number, moderator?: Keypair) =>
+    program.methods
+      .moderateTale(state, 0)
+      .accountsPartial({ platformConfig, taleAccount: tale, moderator: moderator ? moderator.publicKey : admin })
+      .signers(moderator ? [moderator] : [])
+      .rpc();
+
+  const moderateEpisode = (episode: PublicKey, state: number, moderator?: Keypair) =>
+    program.methods
+      .moderateEpisode(state, 0)
+      .accountsPartial({ platformConfig, episodeAccount: episode, moderator: moderator ? moderator.publicKey : admin })
+      .signers(moderator ? [moderator] : [])
+      .rpc();
+
+  before(async () => {
+    const programData = PublicKey.findProgramAddressSync([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE)[0];
+    await program.methods
+      .initializePlatform(FEE_BPS)
+      .accountsPartial({ platformConfig, treasury, program: program.programId, programData, admin, systemProgram })
+      .rpc();
+    await program.methods
+      .createGenre(GENRE, "Fantasy")
+      .accountsPartial({ platformConfig, genreAccount, admin, systemProgram })
+      .rpc();
+  });
+
+  describe("likes", () => {
+    const likePda = (target: PublicKey, user: PublicKey) =>
+      pda(Buffer.from("like"), target.toBuffer(), user.toBuffer());
+    let reader: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const likeTale = (user: Keypair) =>
+      program.methods
+        .likeTale()
+        .accountsPartial({ taleAccount: tale, likeRecord: likePda(tale, user.publicKey), user: user.publicKey, systemProgram })
+        .signers([user])
+        .rpc();
+    const unlikeTale = (user: Keypair) =>
+      program.methods
+        .unlikeTale()
+        .accountsPartial({ taleAccount: tale, likeRecord: likePda(tale, user.publicKey), user: user.publicKey })
+        .signers([user])
+        .rpc();
+
+    before(async () => {
+      const author = await newWallet();
+      reader = await newWallet();
+      tale = await createTale(author, "liked-tale");
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("records one like per wallet and refunds it on unlike", async () => {
+      await likeTale(reader);
+      expect((await program.account.tale.fetch(tale)).likeCount.toNumber()).to.equal(1);
+      const record = await program.account.likeRecord.fetch(likePda(tale, reader.publicKey));
+      expect(record.user.toBase58()).to.equal(reader.publicKey.toBase58());
+
+      await expectFailure(likeTale(reader));
+      expect((await program.account.tale.fetch(tale)).likeCount.toNumber()).to.equal(1);
+
+      await unlikeTale(reader);
+      expect((await program.account.tale.fetch(tale)).likeCount.toNumber()).to.equal(0);
+      expect(await program.account.likeRecord.fetchNullable(likePda(tale, reader.publicKey))).to.equal(null);
+    });
+
+    it("rejects unliking without a like", async () => {
+      await expectError(unlikeTale(reader), "AccountNotInitialized");
+    });
+
+    it("counts likes on an episode from different wallets", async () => {
+      const other = await newWallet();
+      for (const user of [reader, other]) {
+        await program.methods
+          .likeEpisode()
+          .accountsPartial({ episodeAccount: episode, likeRecord: likePda(episode, user.publicKey), user: user.publicKey, systemProgram })
+          .signers([user])
+          .rpc();
+      }
+      expect((await program.account.episode.fetch(episode)).likeCount.toNumber()).to.equal(2);
+
+      await program.methods
+        .unlikeEpisode()
+        .accountsPartial({ episodeAccount: episode, likeRecord: likePda(episode, other.publicKey), user: other.publicKey })
+        .signers([other])
+        .rpc();
+      expect((await program.account.episode.fetch(episode)).likeCount.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("read access", () => {
+    let author: Keypair;
+    let reader: Keypair;
+
+    const setGates = (tale: PublicKey, governance: number | null, earlyAccess: number | null, collectionGates: number) =>
+      program.methods
+        .setTaleGates(
+          governance === null ? null : { mint: Keypair.generate().publicKey, minBalance: new BN(governance) },
+          earlyAccess === null ? null : { mint: Keypair.generate().publicKey, minBalance: new BN(earlyAccess) },
+          null,
+          collectionGates
+        )
+        .accountsPartial({ taleAccount: tale, author: author.publicKey })
+        .signers([author])
+        .rpc();
+    const grantReadAccess = (tale: PublicKey) =>
+      program.methods
+        .grantReadAccess()
+        .accountsPartial({
+          taleAccount: tale,
+          accessPass: accessPda(tale, reader.publicKey),
+          reader: reader.publicKey,
+          systemProgram,
+        })
+        .signers([reader])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      reader = await newWallet();
+    });
+
+    it("grants a pass on an ungated published tale", async () => {
+      const tale = await createTale(author, "open-tale");
+      await grantReadAccess(tale);
+      const pass = await program.account.accessPass.fetch(accessPda(tale, reader.publicKey));
+      expect(pass.tale.toBase58()).to.equal(tale.toBase58());
+      expect(pass.reader.toBase58()).to.equal(reader.publicKey.toBase58());
+    });
+
+    it("refuses passes on draft tales", async () => {
+      const tale = await createTale(author, "draft-tale", TALE_DRAFT);
+      await expectError(grantReadAccess(tale), "TaleNotPublished");
+    });
+
+    it("validates gate configuration", async () => {
+      const tale = await createTale(author, "gate-config");
+      await expectError(setGates(tale, 0, null, 0), "InvalidGateMinBalance");
+      await expectError(setGates(tale, 2, null, GATE_GOVERNANCE), "InvalidCollectionGateMinBalance");
+      await expectError(setGates(tale, 1, null, GATE_EARLY_ACCESS), "InvalidCollectionGates");
+
+      await setGates(tale, 5, 1, GATE_EARLY_ACCESS);
+      const configured = await program.account.tale.fetch(tale);
+      expect(configured.isGovernanceTokenGated).to.equal(true);
+      expect(configured.isEarlyAccessTokenGated).to.equal(true);
+      expect(configured.isRealWorldAssetGated).to.equal(false);
+      expect(configured.collectionGates).to.equal(GATE_EARLY_ACCESS);
+    });
+
+    it("requires a token account for every enabled gate", async () => {
+      const tale = await createTale(author, "gated-tale");
+      await setGates(tale, 1, null, 0);
+      await expectError(grantReadAccess(tale), "MissingGateTokenAccount");
+
+      // A wallet is not a token account.
+      await expectError(
+        program.methods
+          .grantReadAccess()
+          .accountsPartial({
+            taleAccount: tale,
+            accessPass: accessPda(tale, reader.publicKey),
+            reader: reader.publicKey,
+            systemProgram,
+          })
+          .remainingAccounts([{ pubkey: reader.publicKey, isSigner: false, isWritable: false }])
+          .signers([reader])
+          .rpc(),
+        "AccountOwnedByWrongProgram"
+      );
+    });
+
+    it("refuses passes on hidden tales", async () => {
+      const tale = await createTale(author, "hidden-tale");
+      await moderateTale(tale, MODERATION_HIDDEN);
+      await expectError(grantReadAccess(tale), "ContentHidden");
+
+      await moderateTale(tale, MODERATION_VISIBLE);
+      await grantReadAccess(tale);
+    });
+  });
+
+  describe("scheduled publishing", () => {
+    let author: Keypair;
+    let tale: PublicKey;
+
+    const episodeStatus = async (episode: PublicKey) => (await program.account.episode.fetch(episode)).status;
+    const scheduleEpisode = (episode: PublicKey, publishAt: number, unpublishAt: number | null = null) =>
+      program.methods
+        .scheduleEpisode(new BN(publishAt), unpublishAt === null ? null : new BN(unpublishAt))
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+    const crank = (episode: PublicKey) =>
+      program.methods.processEpisodeSchedule().accountsPartial({ episodeAccount: episode }).rpc();
+
+    before(async () => {
+      author = await newWallet();
+      tale = await createTale(author, "scheduled-tale");
+    });
+
+    it("publishes and archives a scheduled episode once its times pass", async () => {
+      const episode = await createEpisode(tale, author, "ep-1", { status: EPISODE_DRAFT });
+      const publishAt = (await chainTime()) + 3;
+      await scheduleEpisode(episode, publishAt, publishAt + 3);
+      expect(await episodeStatus(episode)).to.equal(EPISODE_SCHEDULED);
+      await expectError(crank(episode), "EpisodeScheduleNotDue");
+
+      await waitUntil(publishAt);
+      await crank(episode);
+      expect(await episodeStatus(episode)).to.equal(EPISODE_PUBLISHED);
+      await expectError(crank(episode), "EpisodeScheduleNotDue");
+
+      await waitUntil(publishAt + 3);
+      await crank(episode);
+      expect(await episodeStatus(episode)).to.equal(EPISODE_ARCHIVED);
+    });
+
+    it("cranks due episodes in a batch and skips the rest", async () => {
+      const publishAt = (await chainTime()) + 3;
+      const due = await createEpisode(tale, author, "ep-2", { status: EPISODE_SCHEDULED, order: 2, publishAt });
+      const later = await createEpisode(tale, author, "ep-3", {
+        status: EPISODE_SCHEDULED,
+        order: 3,
+        publishAt: publishAt + 3600,
+      });
+
+      await waitUntil(publishAt);
+      await program.methods
+        .processEpisodeSchedules()
+        .remainingAccounts([
+          { pubkey: due, isSigner: false, isWritable: true },
+          { pubkey: later, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      expect(await episodeStatus(due)).to.equal(EPISODE_PUBLISHED);
+      expect(await episodeStatus(later)).to.equal(EPISODE_SCHEDULED);
+    });
+
+    it("rejects schedules that are missing, inverted or already past", async () => {
+      const now = await chainTime();
+      await expectError(createEpisode(tale, author, "ep-4", { status: EPISODE_SCHEDULED }), "MissingPublishTime");
+      await expectError(
+        createEpisode(tale, author, "ep-4", { status: EPISODE_SCHEDULED, publishAt: now - 60 }),
+        "PublishTimeInPast"
+      );
+
+      const episode = await createEpisode(tale, author, "ep-4", { status: EPISODE_DRAFT, order: 4 });
+      await expectError(scheduleEpisode(episode, now - 60), "PublishTimeInPast");
+      await expectError(scheduleEpisode(episode, now + 3600, now + 60), "InvalidScheduleTimes");
+
+      await scheduleEpisode(episode, now + 3600);
+      await expectError(patchEpisode(tale, episode, author, { publishAtTime: new BN(now - 60) }), "PublishTimeInPast");
+      await expectError(
+        program.methods
+          .updateEpisode("Episode ep-4", "bafyepisodecid", "", "", 4, EPISODE_SCHEDULED, false, "", new BN(now - 60), null)
+          .accountsPartial({
+            episodeAccount: episode,
+            parentTaleAccount: tale,
+            collaborator: null,
+            revisionAccount: null,
+            editor: author.publicKey,
+            systemProgram,
+          })
+          .signers([author])
+          .rpc(),
+        "PublishTimeInPast"
+      );
+
+      // Keeping the stored publish time is allowed, and unscheduling clears it.
+      await patchEpisode(tale, episode, author, { episodeName: "Renamed" });
+      await program.methods
+        .unscheduleEpisode()
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+      const unscheduled = await program.account.episode.fetch(episode);
+      expect(unscheduled.status).to.equal(EPISODE_DRAFT);
+      expect(unscheduled.publishAtTime).to.equal(null);
+    });
+  });
+
+  describe("status transitions", () => {
+    let author: Keypair;
+
+    const changeTaleStatus = (method: "publishTale" | "archiveTale" | "restoreTale", tale: PublicKey) =>
+      program.methods[method]()
+        .accountsPartial({ taleAccount: tale, author: author.publicKey })
+        .signers([author])
+        .rpc();
+    const changeEpisodeStatus = (
+      method: "publishEpisode" | "archiveEpisode" | "restoreEpisode",
+      tale: PublicKey,
+      episode: PublicKey
+    ) =>
+      program.methods[method]()
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+    });
+
+    it("moves tales only along the allowed transitions", async () => {
+      const tale = await createTale(author, "status-tale", TALE_DRAFT);
+      await changeTaleStatus("publishTale", tale);
+      expect((await program.account.tale.fetch(tale)).status).to.equal(TALE_PUBLISHED);
+      await expectError(changeTaleStatus("restoreTale", tale), "InvalidTaleStatusTransition");
+
+      await changeTaleStatus("archiveTale", tale);
+      expect((await program.account.tale.fetch(tale)).status).to.equal(TALE_ARCHIVED);
+      await expectError(changeTaleStatus("publishTale", tale), "InvalidTaleStatusTransition");
+
+      await changeTaleStatus("restoreTale", tale);
+      expect((await program.account.tale.fetch(tale)).status).to.equal(TALE_DRAFT);
+    });
+
+    it("never moves a published episode back to draft", async () => {
+      const tale = await createTale(author, "status-episodes");
+      const episode = await createEpisode(tale, author, "ep-1", { status: EPISODE_DRAFT });
+      await changeEpisodeStatus("publishEpisode", tale, episode);
+      await expectError(changeEpisodeStatus("restoreEpisode", tale, episode), "InvalidEpisodeStatusTransition");
+
+      await changeEpisodeStatus("archiveEpisode", tale, episode);
+      await expectError(changeEpisodeStatus("publishEpisode", tale, episode), "InvalidEpisodeStatusTransition");
+
+      await changeEpisodeStatus("restoreEpisode", tale, episode);
+      expect((await program.account.episode.fetch(episode)).status).to.equal(EPISODE_DRAFT);
+    });
+  });
+
+  describe("collaborator roles", () => {
+    let author: Keypair;
+    let editor: Keypair;
+    let manager: Keypair;
+    let outsider: Keypair;
+    let tale: PublicKey;
+
+    before(async () => {
+      author = await newWallet();
+      editor = await newWallet();
+      manager = await newWallet();
+      outsider = await newWallet();
+      tale = await createTale(author, "shared-tale");
+    });
+
+    it("lets a collaborator use the roles they were granted", async () => {
+      await expectError(addCollaborator(tale, author, editor.publicKey, 0), "InvalidCollaboratorRoles");
+      await addCollaborator(tale, author, editor.publicKey, ROLE_EDIT_EPISODES);
+
+      const episode = await createEpisode(tale, editor, "ep-1", { status: EPISODE_DRAFT, withCollaborator: true });
+      expect((await program.account.episode.fetch(episode)).author.toBase58()).to.equal(editor.publicKey.toBase58());
+      await expectError(
+        program.methods
+          .publishEpisode()
+          .accountsPartial({
+            episodeAccount: episode,
+            parentTaleAccount: tale,
+            collaborator: collaboratorPda(tale, editor.publicKey),
+            editor: editor.publicKey,
+          })
+          .signers([editor])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("rejects wallets that are not collaborators", async () => {
+      await expectError(createEpisode(tale, outsider, "ep-2"), "Unauthorized");
+    });
+
+    it("keeps the manage role with the author", async () => {
+      await addCollaborator(tale, author, manager.publicKey, ROLE_MANAGE_COLLABORATORS);
+      await addCollaborator(tale, manager, outsider.publicKey, ROLE_EDIT_EPISODES | ROLE_PUBLISH, true);
+      const added = await program.account.taleCollaborator.fetch(collaboratorPda(tale, outsider.publicKey));
+      expect(added.roles).to.equal(ROLE_EDIT_EPISODES | ROLE_PUBLISH);
+      expect(added.addedBy.toBase58()).to.equal(manager.publicKey.toBase58());
+
+      await expectError(
+        addCollaborator(tale, manager, Keypair.generate().publicKey, ROLE_MANAGE_COLLABORATORS, true),
+        "CannotManageCollaboratorManagers"
+      );
+    });
+
+    it("revokes access when a collaborator is removed", async () => {
+      await program.methods
+        .removeCollaborator()
+        .accountsPartial({
+          taleAccount: tale,
+          collaborator: collaboratorPda(tale, editor.publicKey),
+          managerCollaborator: null,
+          manager: author.publicKey,
+          addedBy: author.publicKey,
+        })
+        .signers([author])
+        .rpc();
+      expect(await program.account.taleCollaborator.fetchNullable(collaboratorPda(tale, editor.publicKey))).to.equal(null);
+      await expectFailure(createEpisode(tale, editor, "ep-3", { withCollaborator: true }));
+    });
+  });
+
+  describe("tale deletion", () => {
+    let author: Keypair;
+
+    const cascade = (tale: PublicKey, records: PublicKey[]) =>
+      program.methods
+        .closeTaleCascade()
+        .accountsPartial({ taleAccount: tale, genreAccount, author: author.publicKey })
+        .remainingAccounts(records.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+        .signers([author])
+        .rpc();
+    const taleCount = async () => (await program.account.genre.fetch(genreAccount)).taleCount.toNumber();
+
+    before(async () => {
+      author = await newWallet();
+    });
+
+    it("refuses to delete published tales", async () => {
+      const tale = await createTale(author, "published-tale");
+      await expectError(deleteTale(tale, author), "CannotDeletePublished");
+    });
+
+    it("only deletes a tale once its episodes are gone", async () => {
+      const tale = await createTale(author, "counted-tale", TALE_DRAFT);
+      const episode = await createEpisode(tale, author, "ep-1");
+      expect((await program.account.tale.fetch(tale)).episodeCount).to.equal(1);
+      await expectError(deleteTale(tale, author), "TaleHasEpisodes");
+
+      await deleteEpisode(tale, episode, author);
+      expect((await program.account.tale.fetch(tale)).episodeCount).to.equal(0);
+
+      const countBefore = await taleCount();
+      await deleteTale(tale, author);
+      expect(await program.account.tale.fetchNullable(tale)).to.equal(null);
+      expect(await taleCount()).to.equal(countBefore - 1);
+    });
+
+    it("closes the remaining episodes and then the tale in a cascade", async () => {
+      const tale = await createTale(author, "cascaded-tale", TALE_DRAFT);
+      const first = await createEpisode(tale, author, "ep-1");
+      const second = await createEpisode(tale, author, "ep-2", { order: 2 });
+      await expectError(cascade(tale, [first]), "InvalidCascadeAccounts");
+
+      await cascade(tale, [first, author.publicKey]);
+      expect(await program.account.episode.fetchNullable(first)).to.equal(null);
+      expect((await program.account.tale.fetch(tale)).episodeCount).to.equal(1);
+
+      const countBefore = await taleCount();
+      await cascade(tale, [second, author.publicKey]);
+      expect(await program.account.episode.fetchNullable(second)).to.equal(null);
+      expect(await program.account.tale.fetchNullable(tale)).to.equal(null);
+      expect(await taleCount()).to.equal(countBefore - 1);
+    });
+  });
+
+  describe("patch updates", () => {
+    let author: Keypair;
+    let tale: PublicKey;
+
+    const emptyTalePatch = {
+      title: null,
+      contentCid: null,
+      genre: null,
+      coverImageCid: null,
+      thumbnailCid: null,
+      candyMachineAddress: null,
+      isGovernanceTokenGated: null,
+      isEarlyAccessTokenGated: null,
+      isRealWorldAssetGated: null,
+    };
+    const patchTale = (signer: Keypair, patch: Partial<typeof emptyTalePatch>) =>
+      program.methods
+        .patchTale({ ...emptyTalePatch, ...patch })
+        .accountsPartial({ taleAccount: tale, genreAccount: null, previousGenre: null, author: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      tale = await createTale(author, "patched-tale");
+    });
+
+    it("changes only the tale fields that are set", async () => {
+      const before = await program.account.tale.fetch(tale);
+      await patchTale(author, { title: "The Longest Night", isEarlyAccessTokenGated: true });
+
+      const after = await program.account.tale.fetch(tale);
+      expect(after.title).to.equal("The Longest Night");
+      expect(after.isEarlyAccessTokenGated).to.equal(true);
+      expect(after.contentCid).to.equal(before.contentCid);
+      expect(after.genre).to.equal(before.genre);
+      expect(after.isGovernanceTokenGated).to.equal(before.isGovernanceTokenGated);
+      expect(after.status).to.equal(before.status);
+    });
+
+    it("validates the fields that are set", async () => {
+      await expectError(patchTale(author, { title: "x".repeat(101) }), "TitleTooLong");
+    });
+
+    it("rejects patches from anyone but the author", async () => {
+      await expectError(patchTale(await newWallet(), { title: "Stolen" }), "Unauthorized");
+    });
+
+    it("changes only the episode fields that are set", async () => {
+      const episode = await createEpisode(tale, author, "ep-1");
+      const before = await program.account.episode.fetch(episode);
+      await patchEpisode(tale, episode, author, { episodeName: "Dawn", order: 7 });
+
+      const after = await program.account.episode.fetch(episode);
+      expect(after.episodeName).to.equal("Dawn");
+      expect(after.order).to.equal(7);
+      expect(after.contentCid).to.equal(before.contentCid);
+      expect(after.status).to.equal(before.status);
+      expect(after.revisionCount).to.equal(0);
+    });
+  });
+
+  describe("legacy account migration", () => {
+    // Loaded from tests/fixtures: a draft tale under the global `[tale, tale_id]` derivation and
+    // one of its episodes, both at their pre-versioning sizes with stale bytes after `like_count`.
+    const legacyAuthor = Keypair.fromSeed(new Uint8Array(32).fill(1));
+    const legacyTale = pda(Buffer.from("tale"), Buffer.from("legacy-tale"));
+    const legacyEpisode = episodePda(legacyTale, "ep-legacy");
+
+    const dataLength = async (account: PublicKey) => (await connection.getAccountInfo(account))!.data.length;
+    const migrateTale = () =>
+      program.methods.migrateTale().accountsPartial({ taleAccount: legacyTale, payer: admin, systemProgram }).rpc();
+    const migrateEpisode = () =>
+      program.methods
+        .migrateEpisode()
+        .accountsPartial({ episodeAccount: legacyEpisode, parentTaleAccount: legacyTale, payer: admin, systemProgram })
+        .rpc();
+
+    before(async () => {
+      await airdrop(legacyAuthor.publicKey);
+    });
+
+    it("starts from 536 and 528 byte accounts", async () => {
+      expect(await dataLength(legacyTale)).to.equal(536);
+      expect(await dataLength(legacyEpisode)).to.equal(528);
+    });
+
+    it("requires the tale to be migrated before its episodes", async () => {
+      await expectError(migrateEpisode(), "AccountDidNotDeserialize");
+    });
+
+    it("migrates a legacy tale once", async () => {
+      await migrateTale();
+      expect(await dataLength(legacyTale)).to.be.greaterThan(536);
+
+      const tale = await program.account.tale.fetch(legacyTale);
+      expect(tale.version).to.equal(1);
+      expect(tale.author.toBase58()).to.equal(legacyAuthor.publicKey.toBase58());
+      expect(tale.taleId).to.equal("legacy-tale");
+      expect(tale.title).to.equal("A Legacy Tale");
+      expect(tale.genre).to.equal(GENRE);
+      expect(tale.likeCount.toNumber()).to.equal(3);
+      expect(tale.creator).to.equal(null);
+      expect(tale.governanceGate).to.equal(null);
+      expect(tale.tipSplits).to.deep.equal([]);
+      expect(tale.episodeCount).to.equal(0);
+
+      await expectError(migrateTale(), "AccountAlreadyMigrated");
+    });
+
+    it("never deletes a legacy tale whose episodes may be uncounted", async () => {
+      await expectError(deleteTale(legacyTale, legacyAuthor), "TaleEpisodesUncounted");
+    });
+
+    it("migrates a legacy episode once and counts it on its tale", async () => {
+      await migrateEpisode();
+      expect(await dataLength(legacyEpisode)).to.be.greaterThan(528);
+
+      const episode = await program.account.episode.fetch(legacyEpisode);
+      expect(episode.version).to.equal(1);
+      expect(episode.parentTale.toBase58()).to.equal(legacyTale.toBase58());
+      expect(episode.episodeName).to.equal("A Legacy Episode");
+      expect(episode.likeCount.toNumber()).to.equal(2);
+      expect(episode.branches).to.deep.equal([]);
+      expect(episode.season).to.equal(null);
+      expect((await program.account.tale.fetch(legacyTale)).episodeCount).to.equal(1);
+
+      await expectError(migrateEpisode(), "AccountAlreadyMigrated");
+      await expectError(deleteTale(legacyTale, legacyAuthor), "TaleHasEpisodes");
+    });
+  });
+
+  describe("episode revisions", () => {
+    let author: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const revisionPda = (index: number) => pda(Buffer.from("revision"), episode.toBuffer(), u32(index));
+    const patchContent = (contentCid: string | null, revisionAccount: PublicKey | null, episodeName: string | null = null) =>
+      program.methods
+        .patchEpisode({ ...emptyEpisodePatch, contentCid, episodeName })
+        .accountsPartial({
+          episodeAccount: episode,
+          parentTaleAccount: tale,
+          collaborator: null,
+          revisionAccount,
+          editor: author.publicKey,
+          systemProgram,
+        })
+        .signers([author])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      tale = await createTale(author, "revised-tale");
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("requires a revision account exactly when the content changes", async () => {
+      await expectError(patchContent("bafyrevisedcid", null), "RevisionAccountRequired");
+      await expectError(patchContent(null, revisionPda(0), "Renamed"), "RevisionAccountNotNeeded");
+      await patchContent(null, null, "Renamed");
+      expect((await program.account.episode.fetch(episode)).revisionCount).to.equal(0);
+    });
+
+    it("snapshots the previous content before it is overwritten", async () => {
+      await patchContent("bafyrevisedcid", revisionPda(0));
+      await patchContent("bafyrevisedcid-2", revisionPda(1));
+
+      const first = await program.account.episodeRevision.fetch(revisionPda(0));
+      expect(first.revision).to.equal(0);
+      expect(first.contentCid).to.equal("bafyepisodecid");
+      expect(first.editor.toBase58()).to.equal(author.publicKey.toBase58());
+      const second = await program.account.episodeRevision.fetch(revisionPda(1));
+      expect(second.contentCid).to.equal("bafyrevisedcid");
+
+      const updated = await program.account.episode.fetch(episode);
+      expect(updated.contentCid).to.equal("bafyrevisedcid-2");
+      expect(updated.revisionCount).to.equal(2);
+    });
+  });
+
+  describe("events", () => {
+    let author: Keypair;
+
+    const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const eventsOf = async (signature: string) => {
+      const tx = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      return Array.from(eventParser.parseLogs(tx!.meta!.logMessages!));
+    };
+    const findEvent = (events: anchor.Event[], name: string) => {
+      const event = events.find((e) => e.name.toLowerCase() === name.toLowerCase());
+      expect(event, `expected a ${name} event`).to.not.equal(undefined);
+      return event!.data as Record<string, any>;
+    };
+
+    before(async () => {
+      author = await newWallet();
+    });
+
+    it("emits a typed event when a tale is created", async () => {
+      const tale = talePda(author.publicKey, "evented-tale");
+      const signature = await program.methods
+        .createTale("evented-tale", "The Long Night", "bafytalecid", GENRE, "", "", TALE_PUBLISHED, null, false, false, false)
+        .accountsPartial({ taleAccount: tale, genreAccount, authorProfile: null, author: author.publicKey, systemProgram })
+        .signers([author])
+        .rpc({ commitment: "confirmed" });
+
+      const created = findEvent(await eventsOf(signature), "TaleCreated");
+      expect(created.tale.toBase58()).to.equal(tale.toBase58());
+      expect(created.author.toBase58()).to.equal(author.publicKey.toBase58());
+      expect(created.taleId).to.equal("evented-tale");
+      expect(created.status).to.equal(TALE_PUBLISHED);
+    });
+
+    it("emits the status event of the new episode status", async () => {
+      const tale = talePda(author.publicKey, "evented-tale");
+      const episode = await createEpisode(tale, author, "ep-1", { status: EPISODE_DRAFT });
+      const signature = await program.methods
+        .publishEpisode()
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc({ commitment: "confirmed" });
+
+      const published = findEvent(await eventsOf(signature), "EpisodePublished");
+      expect(published.episode.toBase58()).to.equal(episode.toBase58());
+      expect(published.parentTale.toBase58()).to.equal(tale.toBase58());
+      expect(published.changedBy.toBase58()).to.equal(author.publicKey.toBase58());
+    });
+  });
+
+  describe("tips", () => {
+    let author: Keypair;
+    let tipper: Keypair;
+    let tale: PublicKey;
+
+    const tip = (taleAccount: PublicKey, authorReceiver: PublicKey, amount: number, splitReceivers: PublicKey[] = []) =>
+      program.methods
+        .tipTale(new BN(amount))
+        .accountsPartial({
+          taleAccount,
+          platformConfig,
+          authorReceiver,
+          treasuryReceiver: treasury,
+          upstreamTale: null,
+          upstreamReceiver: null,
+          mint: null,
+          tipperTokenAccount: null,
+          tokenProgram: null,
+          tipper: tipper.publicKey,
+          systemProgram,
+        })
+        .remainingAccounts(splitReceivers.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+        .signers([tipper])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      tipper = await newWallet();
+      tale = await createTale(author, "tipped-tale");
+    });
+
+    it("funds the treasury to rent exemption on initialization", async () => {
+      expect(await balance(treasury)).to.be.at.least(await connection.getMinimumBalanceForRentExemption(0));
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      expect(config.feeBps).to.equal(FEE_BPS);
+      expect(config.treasury.toBase58()).to.equal(treasury.toBase58());
+    });
+
+    it("pays the platform fee to the treasury and the rest to the author", async () => {
+      const amount = 1_000_000;
+      const [authorBefore, treasuryBefore] = [await balance(author.publicKey), await balance(treasury)];
+      await tip(tale, author.publicKey, amount);
+
+      expect((await balance(treasury)) - treasuryBefore).to.equal(fee(amount));
+      expect((await balance(author.publicKey)) - authorBefore).to.equal(amount - fee(amount));
+      const tipped = await program.account.tale.fetch(tale);
+      expect(tipped.totalTips.toNumber()).to.equal(1);
+      expect(tipped.totalTipLamports.toNumber()).to.equal(amount);
+    });
+
+    it("accepts fees smaller than the rent-exempt minimum", async () => {
+      const treasuryBefore = await balance(treasury);
+      await tip(tale, author.publicKey, 1_000);
+      expect((await balance(treasury)) - treasuryBefore).to.equal(fee(1_000));
+    });
+
+    it("rejects empty tips, unpublished tales and a receiver that is not the author", async () => {
+      await expectError(tip(tale, author.publicKey, 0), "InvalidAmount");
+      await expectError(tip(tale, tipper.publicKey, 1_000_000), "InvalidPaymentRecipient");
+      const draft = await createTale(author, "untipped-tale", TALE_DRAFT);
+      await expectError(tip(draft, author.publicKey, 1_000_000), "TaleNotPublished");
+    });
+
+    it("routes a collaborator's split of the tip after the platform fee", async () => {
+      const collaborator = await newWallet();
+      const shareBps = 2_000;
+      await addCollaborator(tale, author, collaborator.publicKey, ROLE_EDIT_EPISODES);
+      await program.methods
+        .setTipSplits([{ wallet: collaborator.publicKey, shareBps }])
+        .accountsPartial({ taleAccount: tale, author: author.publicKey })
+        .remainingAccounts([{ pubkey: collaboratorPda(tale, collaborator.publicKey), isSigner: false, isWritable: false }])
+        .signers([author])
+        .rpc();
+
+      const amount = 1_000_000;
+      const afterFee = amount - fee(amount);
+      const share = Math.floor((afterFee * shareBps) / 10_000);
+      const [authorBefore, collaboratorBefore] = [await balance(author.publicKey), await balance(collaborator.publicKey)];
+      await expectError(tip(tale, author.publicKey, amount), "MissingPaymentRecipient");
+      await tip(tale, author.publicKey, amount, [collaborator.publicKey]);
+
+      expect((await balance(collaborator.publicKey)) - collaboratorBefore).to.equal(share);
+      expect((await balance(author.publicKey)) - authorBefore).to.equal(afterFee - share);
+    });
+  });
+
+  describe("treasury withdrawals", () => {
+    let destination: PublicKey;
+
+    const withdraw = (amount: number, signer?: Keypair) =>
+      program.methods
+        .withdrawTreasury(new BN(amount))
+        .accountsPartial({
+          platformConfig,
+          treasury,
+          destination,
+          mint: null,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+          admin: signer ? signer.publicKey : admin,
+          systemProgram,
+        })
+        .signers(signer ? [signer] : [])
+        .rpc();
+
+    before(async () => {
+      destination = (await newWallet()).publicKey;
+    });
+
+    it("rejects withdrawals by anyone but the admin", async () => {
+      await expectError(withdraw(1, await newWallet()), "Unauthorized");
+    });
+
+    it("withdraws everything above the rent-exempt minimum and no more", async () => {
+      const rentExempt = await connection.getMinimumBalanceForRentExemption(0);
+      const withdrawable = (await balance(treasury)) - rentExempt;
+      expect(withdrawable).to.be.greaterThan(0);
+
+      const destinationBefore = await balance(destination);
+      await withdraw(withdrawable);
+      expect((await balance(destination)) - destinationBefore).to.equal(withdrawable);
+      expect(await balance(treasury)).to.equal(rentExempt);
+      await expectError(withdraw(1), "InsufficientTreasuryBalance");
+    });
+  });
+
+  describe("purchases", () => {
+    const price = 2_000_000;
+    let author: Keypair;
+    let reader: Keypair;
+    let tale: PublicKey;
+
+    const setPrice = (taleAccount: PublicKey, episode: PublicKey, amount: number) =>
+      program.methods
+        .setEpisodePrice(new BN(amount), null)
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: taleAccount, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+    const purchase = (taleAccount: PublicKey, episode: PublicKey) =>
+      program.methods
+        .purchaseEpisode()
+        .accountsPartial({
+          purchaseAccount: purchasePda(episode, reader.publicKey),
+          episodeAccount: episode,
+          parentTaleAccount: taleAccount,
+          platformConfig,
+          authorReceiver: author.publicKey,
+          treasuryReceiver: treasury,
+          upstreamTale: null,
+          upstreamReceiver: null,
+          mint: null,
+          readerTokenAccount: null,
+          tokenProgram: null,
+          reader: reader.publicKey,
+          systemProgram,
+        })
+        .signers([reader])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      reader = await newWallet();
+      tale = await createTale(author, "paid-tale");
+    });
+
+    it("pays the episode price, less the platform fee, to the author", async () => {
+      const episode = await createEpisode(tale, author, "ep-1");
+      await expectError(purchase(tale, episode), "EpisodeNotForSale");
+      await setPrice(tale, episode, price);
+
+      const [authorBefore, treasuryBefore] = [await balance(author.publicKey), await balance(treasury)];
+      await purchase(tale, episode);
+
+      expect((await balance(treasury)) - treasuryBefore).to.equal(fee(price));
+      expect((await balance(author.publicKey)) - authorBefore).to.equal(price - fee(price));
+      const receipt = await program.account.episodePurchase.fetch(purchasePda(episode, reader.publicKey));
+      expect(receipt.amount.toNumber()).to.equal(price);
+      expect(receipt.reader.toBase58()).to.equal(reader.publicKey.toBase58());
+
+      await expectFailure(purchase(tale, episode));
+    });
+
+    it("only sells published episodes of published tales", async () => {
+      const draftEpisode = await createEpisode(tale, author, "ep-2", { status: EPISODE_DRAFT, order: 2 });
+      await setPrice(tale, draftEpisode, price);
+      await expectError(purchase(tale, draftEpisode), "EpisodeNotPublished");
+
+      const draftTale = await createTale(author, "unpublished-paid-tale", TALE_DRAFT);
+      const episode = await createEpisode(draftTale, author, "ep-1");
+      await setPrice(draftTale, episode, price);
+      await expectError(purchase(draftTale, episode), "TaleNotPublished");
+    });
+
+    it("does not sell hidden episodes or episodes of hidden tales", async () => {
+      const episode = await createEpisode(tale, author, "ep-3", { order: 3 });
+      await setPrice(tale, episode, price);
+      await moderateEpisode(episode, MODERATION_HIDDEN);
+      await expectError(purchase(tale, episode), "ContentHidden");
+      await moderateEpisode(episode, MODERATION_VISIBLE);
+
+      await moderateTale(tale, MODERATION_HIDDEN);
+      await expectError(purchase(tale, episode), "ContentHidden");
+      await moderateTale(tale, MODERATION_VISIBLE);
+      await purchase(tale, episode);
+    });
+  });
+
+  describe("subscriptions", () => {
+    const price = 3_000_000;
+    const period = 3_600;
+    let author: Keypair;
+    let reader: Keypair;
+    let tale: PublicKey;
+    let paidEpisode: PublicKey;
+
+    const plan = () => pda(Buffer.from("plan"), tale.toBuffer());
+    const subscription = () => subscriptionPda(tale, reader.publicKey);
+    const payment = () => ({
+      taleAccount: tale,
+      subscriptionPlan: plan(),
+      platformConfig,
+      authorReceiver: author.publicKey,
+      treasuryReceiver: treasury,
+      upstreamTale: null,
+      upstreamReceiver: null,
+      mint: null,
+      readerTokenAccount: null,
+      tokenProgram: null,
+      reader: reader.publicKey,
+    });
+    const renew = () =>
+      program.methods
+        .renewSubscription()
+        .accountsPartial({ payment: payment(), subscription: subscription(), systemProgram })
+        .signers([reader])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      reader = await newWallet();
+      tale = await createTale(author, "subscribed-tale");
+      paidEpisode = await createEpisode(tale, author, "ep-1");
+      await program.methods
+        .setEpisodePrice(new BN(1_000_000), null)
+        .accountsPartial({ episodeAccount: paidEpisode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+    });
+
+    it("validates the subscription plan", async () => {
+      await expectError(
+        program.methods
+          .setSubscriptionPlan(new BN(price), new BN(0), null)
+          .accountsPartial({ taleAccount: tale, subscriptionPlan: plan(), author: author.publicKey, systemProgram })
+          .signers([author])
+          .rpc(),
+        "InvalidSubscriptionPlan"
+      );
+    });
+
+    it("charges one period on subscribe and extends it on renewal", async () => {
+      await program.methods
+        .setSubscriptionPlan(new BN(price), new BN(period), null)
+        .accountsPartial({ taleAccount: tale, subscriptionPlan: plan(), author: author.publicKey, systemProgram })
+        .signers([author])
+        .rpc();
+
+      const [authorBefore, treasuryBefore] = [await balance(author.publicKey), await balance(treasury)];
+      await program.methods
+        .subscribe()
+        .accountsPartial({ payment: payment(), subscription: subscription(), systemProgram })
+        .signers([reader])
+        .rpc();
+      const subscribed = await program.account.subscription.fetch(subscription());
+      expect(subscribed.expiresAt.sub(subscribed.startedAt).toNumber()).to.equal(period);
+
+      await renew();
+      const renewed = await program.account.subscription.fetch(subscription());
+      expect(renewed.expiresAt.sub(subscribed.expiresAt).toNumber()).to.equal(period);
+
+      expect((await balance(treasury)) - treasuryBefore).to.equal(2 * fee(price));
+      expect((await balance(author.publicKey)) - authorBefore).to.equal(2 * (price - fee(price)));
+    });
+
+    it("grants access to paid episodes through a purchase or an active subscription", async () => {
+      const freeEpisode = await createEpisode(tale, author, "ep-2", { order: 2 });
+      const outsider = Keypair.generate().publicKey;
+      await verifyEpisodeAccess(tale, freeEpisode, outsider);
+      await expectError(verifyEpisodeAccess(tale, paidEpisode, outsider), "EpisodeAccessDenied");
+      await verifyEpisodeAccess(tale, paidEpisode, reader.publicKey, { subscribed: true });
+    });
+
+    it("closes the subscription on cancel", async () => {
+      await program.methods
+        .cancelSubscription()
+        .accountsPartial({ subscription: subscription(), reader: reader.publicKey })
+        .signers([reader])
+        .rpc();
+      expect(await program.account.subscription.fetchNullable(subscription())).to.equal(null);
+      await expectError(verifyEpisodeAccess(tale, paidEpisode, reader.publicKey), "EpisodeAccessDenied");
+    });
+
+    it("does not renew subscriptions to unpublished tales", async () => {
+      await program.methods
+        .subscribe()
+        .accountsPartial({ payment: payment(), subscription: subscription(), systemProgram })
+        .signers([reader])
+        .rpc();
+      await program.methods
+        .archiveTale()
+        .accountsPartial({ taleAccount: tale, author: author.publicKey })
+        .signers([author])
+        .rpc();
+      await expectError(renew(), "TaleNotPublished");
+    });
+  });
+
+  describe("comments", () => {
+    let author: Keypair;
+    let reader: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const commentPda = (index: number) => pda(Buffer.from("comment"), episode.toBuffer(), u32(index));
+    const postComment = (commenter: Keypair, index: number, body: string, parent: number | null = null) =>
+      program.methods
+        .postComment(body, parent)
+        .accountsPartial({ episodeAccount: episode, comment: commentPda(index), author: commenter.publicKey, systemProgram })
+        .signers([commenter])
+        .rpc();
+    const editComment = (editor: Keypair, index: number, body: string) =>
+      program.methods
+        .editComment(body)
+        .accountsPartial({ comment: commentPda(index), author: editor.publicKey })
+        .signers([editor])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      reader = await newWallet();
+      tale = await createTale(author, "commented-tale");
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("threads replies under earlier comments", async () => {
+      await postComment(reader, 0, "First!");
+      await expectError(postComment(author, 1, "Reply to nothing", 1), "InvalidParentComment");
+      await postComment(author, 1, "Thanks for reading", 0);
+
+      const reply = await program.account.comment.fetch(commentPda(1));
+      expect(reply.parent).to.equal(0);
+      expect(reply.author.toBase58()).to.equal(author.publicKey.toBase58());
+      expect((await program.account.episode.fetch(episode)).commentCount).to.equal(2);
+    });
+
+    it("rejects comments on unpublished episodes", async () => {
+      const draft = await createEpisode(tale, author, "ep-2", { status: EPISODE_DRAFT, order: 2 });
+      await expectError(
+        program.methods
+          .postComment("Too early", null)
+          .accountsPartial({
+            episodeAccount: draft,
+            comment: pda(Buffer.from("comment"), draft.toBuffer(), u32(0)),
+            author: reader.publicKey,
+            systemProgram,
+          })
+          .signers([reader])
+          .rpc(),
+        "EpisodeNotPublished"
+      );
+    });
+
+    it("lets only the commenter edit or delete a comment", async () => {
+      await expectError(editComment(author, 0, "Edited by someone else"), "Unauthorized");
+      await editComment(reader, 0, "First! (edited)");
+      const edited = await program.account.comment.fetch(commentPda(0));
+      expect(edited.body).to.equal("First! (edited)");
+      expect(edited.editedAt).to.not.equal(null);
+
+      await program.methods
+        .deleteComment()
+        .accountsPartial({ comment: commentPda(0), author: reader.publicKey })
+        .signers([reader])
+        .rpc();
+      expect(await program.account.comment.fetchNullable(commentPda(0))).to.equal(null);
+      // Indices are never reused.
+      expect((await program.account.episode.fetch(episode)).commentCount).to.equal(2);
+    });
+
+    it("lets the tale author hide comments", async () => {
+      const setHidden = (signer: Keypair, hidden: boolean) =>
+        program.methods
+          .setCommentHidden(hidden)
+          .accountsPartial({
+            comment: commentPda(1),
+            episodeAccount: episode,
+            parentTaleAccount: tale,
+            taleAuthor: signer.publicKey,
+          })
+          .signers([signer])
+          .rpc();
+
+      await expectError(setHidden(reader, true), "Unauthorized");
+      await setHidden(author, true);
+      expect((await program.account.comment.fetch(commentPda(1))).isHidden).to.equal(true);
+    });
+  });
+
+  describe("reader progress", () => {
+    let reader: Keypair;
+    let tale: PublicKey;
+    let first: PublicKey;
+    let second: PublicKey;
+
+    const progressPda = (wallet: PublicKey) => pda(Buffer.from("progress"), tale.toBuffer(), wallet.toBuffer());
+    const read = (wallet: Keypair, episode: PublicKey) =>
+      program.methods
+        .updateProgress()
+        .accountsPartial({
+          taleAccount: tale,
+          episodeAccount: episode,
+          progress: progressPda(wallet.publicKey),
+          reader: wallet.publicKey,
+          systemProgram,
+        })
+        .signers([wallet])
+        .rpc();
+    const addBookmark = (episode: PublicKey) =>
+      program.methods
+        .addBookmark()
+        .accountsPartial({ progress: progressPda(reader.publicKey), episodeAccount: episode, reader: reader.publicKey })
+        .signers([reader])
+        .rpc();
+    const removeBookmark = (episode: PublicKey) =>
+      program.methods
+        .removeBookmark(episode)
+        .accountsPartial({ progress: progressPda(reader.publicKey), reader: reader.publicKey })
+        .signers([reader])
+        .rpc();
+
+    before(async () => {
+      const author = await newWallet();
+      reader = await newWallet();
+      tale = await createTale(author, "tracked-tale");
+      first = await createEpisode(tale, author, "ep-1");
+      second = await createEpisode(tale, author, "ep-2", { order: 2 });
+    });
+
+    it("tracks the furthest episode read", async () => {
+      await read(reader, second);
+      let tracked = await program.account.readerProgress.fetch(progressPda(reader.publicKey));
+      expect(tracked.lastEpisode.toBase58()).to.equal(second.toBase58());
+      expect(tracked.highestOrder).to.equal(2);
+      expect(tracked.episodesRead).to.equal(1);
+
+      // Going back does not count as reading further.
+      await read(reader, first);
+      tracked = await program.account.readerProgress.fetch(progressPda(reader.publicKey));
+      expect(tracked.lastOrder).to.equal(1);
+      expect(tracked.highestOrder).to.equal(2);
+      expect(tracked.episodesRead).to.equal(1);
+      expect(tracked.completedAt).to.equal(null);
+    });
+
+    it("marks the tale completed once every episode is read in order", async () => {
+      const other = await newWallet();
+      await read(other, first);
+      await read(other, second);
+      const tracked = await program.account.readerProgress.fetch(progressPda(other.publicKey));
+      expect(tracked.episodesRead).to.equal(2);
+      expect(tracked.completedAt).to.not.equal(null);
+    });
+
+    it("keeps each bookmark once", async () => {
+      await addBookmark(first);
+      await expectError(addBookmark(first), "BookmarkExists");
+      await addBookmark(second);
+      expect((await program.account.readerProgress.fetch(progressPda(reader.publicKey))).bookmarks.length).to.equal(2);
+
+      await removeBookmark(first);
+      await expectError(removeBookmark(first), "BookmarkNotFound");
+      const bookmarks = (await program.account.readerProgress.fetch(progressPda(reader.publicKey))).bookmarks;
+      expect(bookmarks.map((bookmark) => bookmark.toBase58())).to.deep.equal([second.toBase58()]);
+    });
+  });
+
+  describe("profiles and handles", () => {
+    let alice: Keypair;
+    let bob: Keypair;
+
+    const profilePda = (wallet: PublicKey) => pda(Buffer.from("profile"), wallet.toBuffer());
+    const handlePda = (handle: string) => pda(Buffer.from("handle"), Buffer.from(handle));
+    const createProfile = (wallet: Keypair, displayName: string) =>
+      program.methods
+        .createProfile(displayName, "", "")
+        .accountsPartial({ profile: profilePda(wallet.publicKey), wallet: wallet.publicKey, systemProgram })
+        .signers([wallet])
+        .rpc();
+    const claimHandle = (wallet: Keypair, handle: string) =>
+      program.methods
+        .claimHandle(handle)
+        .accountsPartial({
+          profile: profilePda(wallet.publicKey),
+          handleAccount: handlePda(handle),
+          wallet: wallet.publicKey,
+          systemProgram,
+        })
+        .signers([wallet])
+        .rpc();
+
+    before(async () => {
+      alice = await newWallet();
+      bob = await newWallet();
+      await createProfile(alice, "Alice");
+      await createProfile(bob, "Bob");
+    });
+
+    it("links the author's profile to new tales", async () => {
+      const tale = talePda(alice.publicKey, "profiled-tale");
+      await program.methods
+        .createTale("profiled-tale", "The Long Night", "bafytalecid", GENRE, "", "", TALE_PUBLISHED, null, false, false, false)
+        .accountsPartial({
+          taleAccount: tale,
+          genreAccount,
+          authorProfile: profilePda(alice.publicKey),
+          author: alice.publicKey,
+          systemProgram,
+        })
+        .signers([alice])
+        .rpc();
+      expect((await program.account.tale.fetch(tale)).authorProfile.toBase58()).to.equal(
+        profilePda(alice.publicKey).toBase58()
+      );
+    });
+
+    it("gives each handle to one profile", async () => {
+      await expectError(claimHandle(alice, "Al"), "InvalidHandle");
+      await claimHandle(alice, "alice_writes");
+      const profile = await program.account.authorProfile.fetch(profilePda(alice.publicKey));
+      expect(profile.handle).to.equal("alice_writes");
+      const handle = await program.account.handle.fetch(handlePda("alice_writes"));
+      expect(handle.owner.toBase58()).to.equal(alice.publicKey.toBase58());
+
+      await expectFailure(claimHandle(bob, "alice_writes"));
+      await expectError(claimHandle(alice, "alice_again"), "ProfileHasHandle");
+    });
+
+    it("moves a handle between profiles when both wallets sign", async () => {
+      await program.methods
+        .transferHandle()
+        .accountsPartial({
+          fromProfile: profilePda(alice.publicKey),
+          toProfile: profilePda(bob.publicKey),
+          handleAccount: handlePda("alice_writes"),
+          fromWallet: alice.publicKey,
+          toWallet: bob.publicKey,
+        })
+        .signers([alice, bob])
+        .rpc();
+      expect((await program.account.authorProfile.fetch(profilePda(alice.publicKey))).handle).to.equal(null);
+      expect((await program.account.authorProfile.fetch(profilePda(bob.publicKey))).handle).to.equal("alice_writes");
+      expect((await program.account.handle.fetch(handlePda("alice_writes"))).owner.toBase58()).to.equal(
+        bob.publicKey.toBase58()
+      );
+    });
+
+    it("frees a released handle for anyone to claim", async () => {
+      await program.methods
+        .releaseHandle()
+        .accountsPartial({ profile: profilePda(bob.publicKey), handleAccount: handlePda("alice_writes"), wallet: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      expect(await program.account.handle.fetchNullable(handlePda("alice_writes"))).to.equal(null);
+      expect((await program.account.authorProfile.fetch(profilePda(bob.publicKey))).handle).to.equal(null);
+
+      await claimHandle(alice, "alice_writes");
+    });
+  });
+
+  describe("genres", () => {
+    let author: Keypair;
+
+    const createGenre = (slug: string, signer?: Keypair) =>
+      program.methods
+        .createGenre(slug, slug)
+        .accountsPartial({
+          platformConfig,
+          genreAccount: genrePda(slug),
+          admin: signer ? signer.publicKey : admin,
+          systemProgram,
+        })
+        .signers(signer ? [signer] : [])
+        .rpc();
+    const taleCount = async (slug: string) => (await program.account.genre.fetch(genrePda(slug))).taleCount.toNumber();
+
+    before(async () => {
+      author = await newWallet();
+      await createGenre("mystery");
+    });
+
+    it("lets only the admin register valid slugs", async () => {
+      await expectError(createGenre("Sci Fi"), "InvalidGenreSlug");
+      await expectError(createGenre("romance", author), "Unauthorized");
+    });
+
+    it("counts tales per genre as they move between genres", async () => {
+      const tale = await createTale(author, "genre-tale");
+      const [fantasyBefore, mysteryBefore] = [await taleCount(GENRE), await taleCount("mystery")];
+      await program.methods
+        .patchTale({
+          title: null,
+          contentCid: null,
+          genre: "mystery",
+          coverImageCid: null,
+          thumbnailCid: null,
+          candyMachineAddress: null,
+          isGovernanceTokenGated: null,
+          isEarlyAccessTokenGated: null,
+          isRealWorldAssetGated: null,
+        })
+        .accountsPartial({
+          taleAccount: tale,
+          genreAccount: genrePda("mystery"),
+          previousGenre: genreAccount,
+          author: author.publicKey,
+        })
+        .signers([author])
+        .rpc();
+
+      expect((await program.account.tale.fetch(tale)).genre).to.equal("mystery");
+      expect(await taleCount(GENRE)).to.equal(fantasyBefore - 1);
+      expect(await taleCount("mystery")).to.equal(mysteryBefore + 1);
+    });
+
+    it("closes tales against their own genre only", async () => {
+      const tale = await createTale(author, "closed-genre-tale", TALE_DRAFT, "mystery");
+      await expectError(deleteTale(tale, author, GENRE), "ConstraintSeeds");
+      await deleteTale(tale, author, "mystery");
+    });
+
+    it("keeps inactive genres out of new tales", async () => {
+      await createGenre("horror");
+      await program.methods
+        .updateGenre(null, false)
+        .accountsPartial({ platformConfig, genreAccount: genrePda("horror"), admin })
+        .rpc();
+      await expectError(createTale(author, "horror-tale", TALE_PUBLISHED, "horror"), "GenreInactive");
+    });
+  });
+
+  describe("moderation", () => {
+    let author: Keypair;
+    let moderator: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const setModerator = (method: "addModerator" | "removeModerator", wallet: PublicKey) =>
+      program.methods[method](wallet).accountsPartial({ platformConfig, admin }).rpc();
+
+    before(async () => {
+      author = await newWallet();
+      moderator = await newWallet();
+      tale = await createTale(author, "moderated-tale");
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("lets only the admin and moderators moderate", async () => {
+      await expectError(moderateTale(tale, MODERATION_HIDDEN, moderator), "NotModerator");
+      await setModerator("addModerator", moderator.publicKey);
+      await expectError(setModerator("addModerator", moderator.publicKey), "ModeratorExists");
+      await expectError(moderateTale(tale, 3, moderator), "InvalidModerationState");
+
+      await moderateTale(tale, MODERATION_HIDDEN, moderator);
+      const hidden = await program.account.tale.fetch(tale);
+      expect(hidden.moderationState).to.equal(MODERATION_HIDDEN);
+      await moderateTale(tale, MODERATION_VISIBLE, moderator);
+
+      await setModerator("removeModerator", moderator.publicKey);
+      await expectError(moderateEpisode(episode, MODERATION_HIDDEN, moderator), "NotModerator");
+    });
+
+    it("gates episode access on the moderation of the episode and its tale", async () => {
+      const reader = Keypair.generate().publicKey;
+      await verifyEpisodeAccess(tale, episode, reader);
+
+      await moderateEpisode(episode, MODERATION_HIDDEN);
+      await expectError(verifyEpisodeAccess(tale, episode, reader), "ContentHidden");
+      await moderateEpisode(episode, MODERATION_VISIBLE);
+
+      await moderateTale(tale, MODERATION_HIDDEN);
+      await expectError(verifyEpisodeAccess(tale, episode, reader), "ContentHidden");
+      await moderateTale(tale, MODERATION_VISIBLE);
+      await verifyEpisodeAccess(tale, episode, reader);
+    });
+
+    it("gates episode access on the status of the episode and its tale", async () => {
+      const reader = Keypair.generate().publicKey;
+      const draftEpisode = await createEpisode(tale, author, "ep-2", { status: EPISODE_DRAFT, order: 2 });
+      await expectError(verifyEpisodeAccess(tale, draftEpisode, reader), "EpisodeNotPublished");
+
+      const draftTale = await createTale(author, "unpublished-moderated-tale", TALE_DRAFT);
+      const publishedEpisode = await createEpisode(draftTale, author, "ep-1");
+      await expectError(verifyEpisodeAccess(draftTale, publishedEpisode, reader), "TaleNotPublished");
+    });
+
+    it("freezes removed content", async () => {
+      await moderateEpisode(episode, MODERATION_REMOVED);
+      await expectError(patchEpisode(tale, episode, author, { episodeName: "Still here" }), "ContentRemoved");
+
+      await moderateTale(tale, MODERATION_REMOVED);
+      await expectError(createEpisode(tale, author, "ep-3", { order: 3 }), "ContentRemoved");
+    });
+  });
+
+  describe("content reports", () => {
+    const REPORT_OPEN = 0;
+    let author: Keypair;
+    let reporter: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const reportPda = (target: PublicKey, wallet: PublicKey) =>
+      pda(Buffer.from("report"), target.toBuffer(), wallet.toBuffer());
+    const report = (wallet: Keypair, episodeAccount: PublicKey | null, reason = 0, taleAccount = tale) =>
+      program.methods
+        .reportContent(reason, "bafyevidencecid")
+        .accountsPartial({
+          platformConfig,
+          taleAccount,
+          episodeAccount,
+          report: reportPda(episodeAccount ?? taleAccount, wallet.publicKey),
+          reporter: wallet.publicKey,
+          systemProgram,
+        })
+        .signers([wallet])
+        .rpc();
+    const closeReport = (
+      method: "resolveReport" | "dismissReport",
+      episodeAccount: PublicKey | null,
+      wallet: PublicKey,
+      moderator?: Keypair
+    ) =>
+      program.methods[method]()
+        .accountsPartial({
+          platformConfig,
+          report: reportPda(episodeAccount ?? tale, wallet),
+          taleAccount: tale,
+          episodeAccount,
+          reporter: wallet,
+          moderator: moderator ? moderator.publicKey : admin,
+        })
+        .signers(moderator ? [moderator] : [])
+        .rpc();
+    const setThreshold = (threshold: number) =>
+      program.methods.setReportHideThreshold(threshold).accountsPartial({ platformConfig, admin }).rpc();
+
+    before(async () => {
+      author = await newWallet();
+      reporter = await newWallet();
+      tale = await createTale(author, "reported-tale");
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("opens one report per reporter and target", async () => {
+      await expectError(report(reporter, episode, 9), "InvalidReportReason");
+      await report(reporter, episode);
+      await expectFailure(report(reporter, episode));
+
+      const opened = await program.account.contentReport.fetch(reportPda(episode, reporter.publicKey));
+      expect(opened.status).to.equal(REPORT_OPEN);
+      expect(opened.target.toBase58()).to.equal(episode.toBase58());
+      expect(opened.tale.toBase58()).to.equal(tale.toBase58());
+      expect((await program.account.episode.fetch(episode)).reportCount).to.equal(1);
+    });
+
+    it("hides content automatically once open reports reach the threshold", async () => {
+      await setThreshold(2);
+      try {
+        await report(await newWallet(), episode);
+        const hidden = await program.account.episode.fetch(episode);
+        expect(hidden.reportCount).to.equal(2);
+        expect(hidden.moderationState).to.equal(MODERATION_HIDDEN);
+      } finally {
+        await setThreshold(0);
+        await moderateEpisode(episode, MODERATION_VISIBLE);
+      }
+    });
+
+    it("lets moderators resolve reports and refunds the reporter", async () => {
+      await expectError(closeReport("resolveReport", episode, reporter.publicKey, reporter), "NotModerator");
+
+      const reporterBefore = await balance(reporter.publicKey);
+      const rent = (await connection.getAccountInfo(reportPda(episode, reporter.publicKey)))!.lamports;
+      await closeReport("resolveReport", episode, reporter.publicKey);
+
+      expect(await program.account.contentReport.fetchNullable(reportPda(episode, reporter.publicKey))).to.equal(null);
+      expect((await balance(reporter.publicKey)) - reporterBefore).to.equal(rent);
+      expect((await program.account.episode.fetch(episode)).reportCount).to.equal(1);
+    });
+
+    it("lets moderators dismiss reports against a tale", async () => {
+      await report(reporter, null);
+      expect((await program.account.tale.fetch(tale)).reportCount).to.equal(1);
+      await closeReport("dismissReport", null, reporter.publicKey);
+      expect((await program.account.tale.fetch(tale)).reportCount).to.equal(0);
+      expect(await program.account.contentReport.fetchNullable(reportPda(tale, reporter.publicKey))).to.equal(null);
+    });
+
+    it("closes reports whose content no longer exists", async () => {
+      const doomed = await createEpisode(tale, author, "ep-2", { order: 2 });
+      await report(reporter, doomed);
+      const closeOrphaned = () =>
+        program.methods
+          .closeOrphanedReport()
+          .accountsPartial({
+            platformConfig,
+            report: reportPda(doomed, reporter.publicKey),
+            targetAccount: doomed,
+            taleAccount: tale,
+            reporter: reporter.publicKey,
+            moderator: admin,
+          })
+          .rpc();
+
+      await expectError(closeOrphaned(), "ReportTargetExists");
+      await deleteEpisode(tale, doomed, author);
+      await closeOrphaned();
+      expect(await program.account.contentReport.fetchNullable(reportPda(doomed, reporter.publicKey))).to.equal(null);
+    });
+  });
+
+  describe("ownership transfer", () => {
+    let seller: Keypair;
+    let buyer: Keypair;
+    let collaborator: Keypair;
+    let tale: PublicKey;
+
+    const propose = (newAuthor: PublicKey) =>
+      program.methods
+        .proposeTaleTransfer(newAuthor)
+        .accountsPartial({ taleAccount: tale, author: seller.publicKey })
+        .signers([seller])
+        .rpc();
+    const accept = (wallet: Keypair) =>
+      program.methods
+        .acceptTaleTransfer()
+        .accountsPartial({ taleAccount: tale, newAuthor: wallet.publicKey })
+        .signers([wallet])
+        .rpc();
+    const cancel = () =>
+      program.methods
+        .cancelTaleTransfer()
+        .accountsPartial({ taleAccount: tale, author: seller.publicKey })
+        .signers([seller])
+        .rpc();
+
+    before(async () => {
+      seller = await newWallet();
+      buyer = await newWallet();
+      collaborator = await newWallet();
+      tale = await createTale(seller, "sold-tale");
+    });
+
+    it("only hands the tale to the proposed wallet", async () => {
+      await expectError(propose(seller.publicKey), "InvalidTransferRecipient");
+      await propose(buyer.publicKey);
+      expect((await program.account.tale.fetch(tale)).pendingAuthor.toBase58()).to.equal(buyer.publicKey.toBase58());
+      await expectError(accept(collaborator), "NotPendingAuthor");
+
+      await cancel();
+      await expectError(cancel(), "NoPendingTransfer");
+      await expectError(accept(buyer), "NotPendingAuthor");
+    });
+
+    it("revokes collaborators granted before the transfer until the new author updates them", async () => {
+      // The seller grants a wallet full roles right before selling.
+      await addCollaborator(
+        tale,
+        seller,
+        collaborator.publicKey,
+        ROLE_EDIT_EPISODES | ROLE_PUBLISH | ROLE_MANAGE_COLLABORATORS
+      );
+      await propose(buyer.publicKey);
+      await accept(buyer);
+
+      const transferred = await program.account.tale.fetch(tale);
+      expect(transferred.author.toBase58()).to.equal(buyer.publicKey.toBase58());
+      expect(transferred.creator.toBase58()).to.equal(seller.publicKey.toBase58());
+      expect(transferred.pendingAuthor).to.equal(null);
+      await expectError(createEpisode(tale, collaborator, "ep-1", { withCollaborator: true }), "Unauthorized");
+      await expectError(createEpisode(tale, seller, "ep-1"), "Unauthorized");
+
+      await program.methods
+        .updateCollaborator(ROLE_EDIT_EPISODES)
+        .accountsPartial({
+          taleAccount: tale,
+          collaborator: collaboratorPda(tale, collaborator.publicKey),
+          managerCollaborator: null,
+          manager: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+      await createEpisode(tale, collaborator, "ep-1", { withCollaborator: true });
+      await createEpisode(tale, buyer, "ep-2", { order: 2 });
+    });
+  });
+
+  describe("branching episodes", () => {
+    let author: Keypair;
+    let tale: PublicKey;
+    let first: PublicKey;
+    let second: PublicKey;
+    let third: PublicKey;
+
+    const branchAccounts = (from: PublicKey, to: PublicKey) => ({
+      episodeAccount: from,
+      nextEpisode: to,
+      parentTaleAccount: tale,
+      collaborator: null,
+      editor: author.publicKey,
+    });
+    const attach = (from: PublicKey, to: PublicKey, label = "Go on") =>
+      program.methods.attachBranch(label).accountsPartial(branchAccounts(from, to)).signers([author]).rpc();
+    const detach = (from: PublicKey, to: PublicKey) =>
+      program.methods.detachBranch().accountsPartial(branchAccounts(from, to)).signers([author]).rpc();
+    const setEnding = (episode: PublicKey, isEnding: boolean) =>
+      program.methods
+        .setEpisodeEnding(isEnding)
+        .accountsPartial({ episodeAccount: episode, parentTaleAccount: tale, collaborator: null, editor: author.publicKey })
+        .signers([author])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      tale = await createTale(author, "branching-tale");
+      first = await createEpisode(tale, author, "ep-1");
+      second = await createEpisode(tale, author, "ep-2", { order: 2 });
+      third = await createEpisode(tale, author, "ep-3", { order: 3 });
+    });
+
+    it("links episodes forward only", async () => {
+      await attach(first, third, "Take the left door");
+      await attach(second, third, "Take the right door");
+      await expectError(attach(first, third), "BranchExists");
+      await expectError(attach(third, first), "InvalidBranchTarget");
+
+      const target = await program.account.episode.fetch(third);
+      expect(target.inboundBranchCount).to.equal(2);
+      expect(target.parentEpisode.toBase58()).to.equal(first.toBase58());
+      const source = await program.account.episode.fetch(first);
+      expect(source.branches.map((b) => b.label)).to.deep.equal(["Take the left door"]);
+    });
+
+    it("locks linked episodes in place", async () => {
+      await expectError(deleteEpisode(tale, third, author), "EpisodeHasBranches");
+      await expectError(patchEpisode(tale, third, author, { order: 9 }), "EpisodeOrderLocked");
+      await expectError(setEnding(first, true), "EndingCannotBranch");
+    });
+
+    it("keeps the parent episode until the last inbound branch is detached", async () => {
+      await detach(first, third);
+      let target = await program.account.episode.fetch(third);
+      expect(target.inboundBranchCount).to.equal(1);
+      expect(target.parentEpisode).to.not.equal(null);
+      await expectError(detach(first, third), "BranchNotFound");
+
+      await detach(second, third);
+      target = await program.account.episode.fetch(third);
+      expect(target.inboundBranchCount).to.equal(0);
+      expect(target.parentEpisode).to.equal(null);
+    });
+
+    it("stops endings from branching", async () => {
+      const epilogue = await createEpisode(tale, author, "ep-4", { order: 4 });
+      await setEnding(third, true);
+      expect((await program.account.episode.fetch(third)).isEnding).to.equal(true);
+      await expectError(attach(third, epilogue), "EndingCannotBranch");
+    });
+  });
+
+  describe("forks", () => {
+    const shareBps = 1_000;
+    let author: Keypair;
+    let forker: Keypair;
+    let tipper: Keypair;
+    let upstream: PublicKey;
+
+    const setForkPolicy = (policy: number, bps: number) =>
+      program.methods
+        .setForkPolicy(policy, bps)
+        .accountsPartial({ taleAccount: upstream, author: author.publicKey })
+        .signers([author])
+        .rpc();
+    const forkApprovalPda = (wallet: PublicKey) =>
+      pda(Buffer.from("fork_approval"), upstream.toBuffer(), wallet.toBuffer());
+    const fork = (taleId: string, withApproval = false) =>
+      program.methods
+        .forkTale(taleId, "The Longer Night", "bafyforkcid", GENRE, "", "")
+        .accountsPartial({
+          create: {
+            taleAccount: talePda(forker.publicKey, taleId),
+            genreAccount,
+            authorProfile: null,
+            author: forker.publicKey,
+            systemProgram,
+          },
+          upstreamTale: upstream,
+          forkApproval: withApproval ? forkApprovalPda(forker.publicKey) : null,
+        })
+        .signers([forker])
+        .rpc();
+    const tipFork = (forkTale: PublicKey, amount: number, withUpstream: boolean) =>
+      program.methods
+        .tipTale(new BN(amount))
+        .accountsPartial({
+          taleAccount: forkTale,
+          platformConfig,
+          authorReceiver: forker.publicKey,
+          treasuryReceiver: treasury,
+          upstreamTale: withUpstream ? upstream : null,
+          upstreamReceiver: withUpstream ? author.publicKey : null,
+          mint: null,
+          tipperTokenAccount: null,
+          tokenProgram: null,
+          tipper: tipper.publicKey,
+          systemProgram,
+        })
+        .signers([tipper])
+        .rpc();
+
+    before(async () => {
+      author = await newWallet();
+      forker = await newWallet();
+      tipper = await newWallet();
+      upstream = await createTale(author, "the-long-night");
+    });
+
+    it("follows the upstream fork policy", async () => {
+      await expectError(fork("disallowed-fork"), "ForkingDisallowed");
+      await expectError(setForkPolicy(FORK_POLICY_REQUIRES_APPROVAL, shareBps), "InvalidForkShare");
+
+      await setForkPolicy(FORK_POLICY_REQUIRES_APPROVAL, 0);
+      await expectError(fork("approved-fork"), "ForkApprovalRequired");
+      await program.methods
+        .approveFork(forker.publicKey)
+        .accountsPartial({
+          taleAccount: upstream,
+          forkApproval: forkApprovalPda(forker.publicKey),
+          author: author.publicKey,
+          systemProgram,
+        })
+        .signers([author])
+        .rpc();
+      await fork("approved-fork", true);
+
+      const forked = await program.account.tale.fetch(talePda(forker.publicKey, "approved-fork"));
+      expect(forked.upstreamTale.toBase58()).to.equal(upstream.toBase58());
+      expect(forked.upstreamShareBps).to.equal(0);
+      expect(forked.status).to.equal(TALE_DRAFT);
+    });
+
+    it("routes the upstream share of a fork's tips to the upstream author", async () => {
+      await setForkPolicy(FORK_POLICY_REVENUE_SHARE, shareBps);
+      await fork("the-longer-night");
+      const forkTale = talePda(forker.publicKey, "the-longer-night");
+      await program.methods
+        .publishTale()
+        .accountsPartial({ taleAccount: forkTale, author: forker.publicKey })
+        .signers([forker])
+        .rpc();
+
+      const amount = 1_000_000;
+      const afterFee = amount - fee(amount);
+      const upstreamAmount = Math.floor((afterFee * shareBps) / 10_000);
+      const [upstreamBefore, forkerBefore, treasuryBefore] = [
+        await balance(author.publicKey),
+        await balance(forker.publicKey),
+        await balance(treasury),
+      ];
+      await expectError(tipFork(forkTale, amount, false), "MissingUpstreamTale");
+      await tipFork(forkTale, amount, true);
+
+      expect((await balance(treasury)) - treasuryBefore).to.equal(fee(amount));
+      expect((await balance(author.publicKey)) - upstreamBefore).to.equal(upstreamAmount);
+      expect((await balance(forker.publicKey)) - forkerBefore).to.equal(afterFee - upstreamAmount);
+    });
+  });
+
+  describe("episode proposals", () => {
+    let author: Keypair;
+    let proposer: Keypair;
+    let tale: PublicKey;
+
+    const proposalPda = (index: number) => pda(Buffer.from("proposal"), tale.toBuffer(), u32(index));
+    const submit = async (episodeName: string) => {
+      const index = (await program.account.tale.fetch(tale)).proposalCount;
+      await program.methods
+        .submitEpisodeProposal(episodeName, "bafyproposalcid", "")
+        .accountsPartial({ taleAccount: tale, proposal: proposalPda(index), proposer: proposer.publicKey, systemProgram })
+        .signers([proposer])
+        .rpc();
+      return proposalPda(index);
+    };
+    const openProposals = async () => (await program.account.tale.fetch(tale)).openProposalCount;
+
+    before(async () => {
+      author = await newWallet();
+      proposer = await newWallet();
+      tale = await createTale(author, "proposed-tale");
+    });
+
+    it("turns an accepted proposal into a draft episode credited to the proposer", async () => {
+      const proposal = await submit("A Proposed Episode");
+      expect(await openProposals()).to.equal(1);
+
+      const episode = episodePda(tale, "ep-proposed");
+      await program.methods
+        .acceptProposal("ep-proposed", 1)
+        .accountsPartial({
+          proposal,
+          episodeAccount: episode,
+          parentTaleAccount: tale,
+          collaborator: null,
+          proposer: proposer.publicKey,
+          editor: author.publicKey,
+          systemProgram,
+        })
+        .signers([author])
+        .rpc();
+
+      const accepted = await program.account.episode.fetch(episode);
+      expect(accepted.author.toBase58()).to.equal(author.publicKey.toBase58());
+      expect(accepted.coAuthor.toBase58()).to.equal(proposer.publicKey.toBase58());
+      expect(accepted.episodeName).to.equal("A Proposed Episode");
+      expect(accepted.status).to.equal(EPISODE_DRAFT);
+      expect(await program.account.episodeProposal.fetchNullable(proposal)).to.equal(null);
+      expect(await openProposals()).to.equal(0);
+      expect((await program.account.tale.fetch(tale)).episodeCount).to.equal(1);
+    });
+
+    it("keeps a tale with open proposals from being deleted until they are resolved", async () => {
+      const proposal = await submit("A Rejected Episode");
+      await program.methods
+        .archiveTale()
+        .accountsPartial({ taleAccount: tale, author: author.publicKey })
+        .signers([author])
+        .rpc();
+      await deleteEpisode(tale, episodePda(tale, "ep-proposed"), author);
+      await expectError(deleteTale(tale, author), "TaleHasProposals");
+
+      await program.methods
+        .rejectProposal()
+        .accountsPartial({
+          proposal,
+          parentTaleAccount: tale,
+          collaborator: null,
+          proposer: proposer.publicKey,
+          editor: author.publicKey,
+        })
+        .signers([author])
+        .rpc();
+      expect(await program.account.episodeProposal.fetchNullable(proposal)).to.equal(null);
+      expect(await openProposals()).to.equal(0);
+      await deleteTale(tale, author);
+    });
+  });
+
+  describe("seasons", () => {
+    let author: Keypair;
+    let tale: PublicKey;
+    let episode: PublicKey;
+
+    const seasonPda = (index: number) => pda(Buffer.from("season"), tale.toBuffer(), u32(index));
+    const createSeason = async (title: string) => {
+      const index = (await program.account.tale.fetch(tale)).nextSeasonIndex;
+      await program.methods
+        .createSeason(title, "")
+        .accountsPartial({ parentTaleAccount: tale, season: seasonPda(index), collaborator: null, editor: author.publicKey, systemProgram })
+        .signers([author])
+        .rpc();
+      return seasonPda(index);
+    };
+    const setSeason = (season: PublicKey | null, previousSeason: PublicKey | null) =>
+      program.methods
+        .setEpisodeSeason()
+        .accountsPartial({
+          episodeAccount: episode,
+          parentTaleAccount: tale,
+          season,
+          previousSeason,
+          collaborator: null,
+          editor: author.publicKey,
+        })
+        .signers([author])
+        .rpc();
+    const closeSeason = (season: PublicKey) =>
+      program.methods
+        .closeSeason()
+        .accountsPartial({ parentTaleAccount: tale, season, collaborator: null, editor: author.publicKey, createdBy: author.publicKey })
+        .signers([author])
+        .rpc();
+    const episodeCount = async (season: PublicKey) => (await program.account.season.fetch(season)).episodeCount;
+
+    before(async () => {
+      author = await newWallet();
+      tale = await createTale(author, "seasoned-tale", TALE_DRAFT);
+      episode = await createEpisode(tale, author, "ep-1");
+    });
+
+    it("moves episodes between seasons and keeps their counts", async () => {
+      const first = await createSeason("Season One");
+      const second = await createSeason("Season Two");
+      expect((await program.account.tale.fetch(tale)).seasonCount).to.equal(2);
+
+      await setSeason(first, null);
+      expect((await program.account.episode.fetch(episode)).season.toBase58()).to.equal(first.toBase58());
+      expect(await episodeCount(first)).to.equal(1);
+
+      await expectError(setSeason(second, null), "SeasonMismatch");
+      await setSeason(second, first);
+      expect(await episodeCount(first)).to.equal(0);
+      expect(await episodeCount(second)).to.equal(1);
+    });
+
+    it("keeps seasons, their episodes and their tale from being closed out of order", async () => {
+      const first = seasonPda(0);
+      const second = seasonPda(1);
+      await expectError(deleteEpisode(tale, episode, author), "EpisodeInSeason");
+      await expectError(closeSeason(second), "SeasonNotEmpty");
+
+      await setSeason(null, second);
+      expect((await program.account.episode.fetch(episode)).season).to.equal(null);
+      await deleteEpisode(tale, episode, author);
+      await expectError(deleteTale(tale, author), "TaleHasSeasons");
+
+      await closeSeason(first);
+      await closeSeason(second);
+      expect(await program.account.season.fetchNullable(second)).to.equal(null);
+      expect((await program.account.tale.fetch(tale)).seasonCount).to.equal(0);
+      await deleteTale(tale, author);
+    });
+  });
+
+  describe("creator namespaces", () => {
+    it("lets different creators use the same tale id", async () => {
+      const first = await newWallet();
+      const second = await newWallet();
+      const firstTale = await createTale(first, "shared-tale-id");
+      const secondTale = await createTale(second, "shared-tale-id");
+      expect(firstTale.toBase58()).to.not.equal(secondTale.toBase58());
+
+      for (const [creator, tale] of [
+        [first, firstTale],
+        [second, secondTale],
+      ] as [Keypair, PublicKey][]) {
+        const account = await program.account.tale.fetch(tale);
+        expect(account.taleId).to.equal("shared-tale-id");
+        expect(account.author.toBase58()).to.equal(creator.publicKey.toBase58());
+        expect(account.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+      }
+      await expectFailure(createTale(first, "shared-tale-id"));
+    });
+  });
+});